@@ -1,7 +1,7 @@
 use core_foundation::{
     CFRunLoopSourceRef, CFStringRef, CFTypeRef, kCFBooleanFalse, kCFBooleanTrue,
 };
-use core_graphics::CGSize;
+use core_graphics::{CGPoint, CGSize};
 use std::cmp::PartialEq;
 use std::ffi::{c_int, c_uint, c_void};
 
@@ -139,6 +139,36 @@ impl TryFrom<AXValueRef> for CGSize {
     }
 }
 
+impl TryFrom<AXValueRef> for CGPoint {
+    type Error = crate::Error;
+
+    fn try_from(ax_value: AXValueRef) -> Result<Self, Self::Error> {
+        let mut point = CGPoint { x: 0.0, y: 0.0 };
+
+        let success = unsafe {
+            AXValueGetValue(
+                ax_value,
+                AXValueType::CG_POINT,
+                &mut point as *mut _ as *mut c_void,
+            )
+        };
+
+        if success {
+            Ok(point)
+        } else {
+            Err(Self::Error::CouldNotExtractValue)
+        }
+    }
+}
+
+impl TryFrom<AXValueRef> for String {
+    type Error = crate::Error;
+
+    fn try_from(ax_value: AXValueRef) -> Result<Self, Self::Error> {
+        String::try_from(CFTypeRef(ax_value.0)).map_err(|_| Self::Error::CouldNotExtractValue)
+    }
+}
+
 impl TryFrom<AXValueRef> for bool {
     type Error = crate::Error;
     fn try_from(ax_value: AXValueRef) -> Result<Self, Self::Error> {
@@ -50,6 +50,8 @@ impl Window {
     const POSITION_ATTR: &'static str = "AXPosition";
     const SIZE_ATTR: &'static str = "AXSize";
     const WINDOWS_ATTR: &'static str = "AXWindows";
+    const ROLE_ATTR: &'static str = "AXRole";
+    const SUBROLE_ATTR: &'static str = "AXSubrole";
     pub const RESIZED_ATTR: &'static str = "AXResized";
     pub const MOVED_ATTR: &'static str = "AXMoved";
 
@@ -103,6 +105,28 @@ impl Window {
         try_get_attr(self.window_ref, Self::MIN_SIZE_ATTR)
     }
 
+    /// Get the on-screen position of the `Window`'s top left corner as a
+    /// `CGPoint`.
+    pub fn position(&self) -> Result<CGPoint> {
+        try_get_attr(self.window_ref, Self::POSITION_ATTR)
+    }
+
+    /// Get the on-screen size of the `Window` as a `CGSize`.
+    pub fn size(&self) -> Result<CGSize> {
+        try_get_attr(self.window_ref, Self::SIZE_ATTR)
+    }
+
+    /// Get the accessibility role of the `Window`, such as `AXWindow`.
+    pub fn role(&self) -> Result<String> {
+        try_get_attr(self.window_ref, Self::ROLE_ATTR)
+    }
+
+    /// Get the accessibility subrole of the `Window`, such as
+    /// `AXStandardWindow` or `AXDialog`.
+    pub fn subrole(&self) -> Result<String> {
+        try_get_attr(self.window_ref, Self::SUBROLE_ATTR)
+    }
+
     /// Get the reference of the `Windows` internal window object.
     pub fn window_ref(&self) -> AxUiElementRef {
         self.window_ref
@@ -2,6 +2,7 @@ use core_foundation::CFArrayRef;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::{
+    cmp::Ordering,
     ffi::c_uint,
     hash::{Hash, Hasher},
     ops::BitOr,
@@ -36,6 +37,18 @@ impl PartialEq<WindowId> for WindowId {
 
 impl Eq for WindowId {}
 
+impl PartialOrd for WindowId {
+    fn partial_cmp(&self, rhs: &WindowId) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl Ord for WindowId {
+    fn cmp(&self, rhs: &WindowId) -> Ordering {
+        self.0.cmp(&rhs.0)
+    }
+}
+
 impl From<u64> for WindowId {
     fn from(value: u64) -> Self {
         Self(value as c_uint)
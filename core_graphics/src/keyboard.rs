@@ -0,0 +1,203 @@
+use crate::bits::EventFlags;
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Left => write!(f, "left"),
+            Self::Right => write!(f, "right"),
+            Self::Up => write!(f, "up"),
+            Self::Down => write!(f, "down"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum Modifier {
+    Shift,
+    Control,
+    Alt,
+    Command,
+}
+
+impl Modifier {
+    const fn bit(self) -> u8 {
+        match self {
+            Self::Shift => 1 << 0,
+            Self::Control => 1 << 1,
+            Self::Alt => 1 << 2,
+            Self::Command => 1 << 3,
+        }
+    }
+}
+
+impl From<EventFlags> for Vec<Modifier> {
+    fn from(flags: EventFlags) -> Self {
+        let mut modifiers = Vec::new();
+        if flags.has_shift() {
+            modifiers.push(Modifier::Shift);
+        }
+        if flags.has_control() {
+            modifiers.push(Modifier::Control);
+        }
+        if flags.has_alt() {
+            modifiers.push(Modifier::Alt);
+        }
+        if flags.has_command() {
+            modifiers.push(Modifier::Command);
+        }
+        modifiers
+    }
+}
+
+// Virtual keycodes from the ANSI layout in Carbon's `Events.h`.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(u16)]
+pub enum Keycode {
+    B = 0x0B,
+    C = 0x08,
+    H = 0x04,
+    V = 0x09,
+    W = 0x0D,
+    Zero = 0x1D,
+    One = 0x12,
+    Two = 0x13,
+    Three = 0x14,
+    Four = 0x15,
+    Five = 0x17,
+    Six = 0x16,
+    Seven = 0x1A,
+    Eight = 0x1C,
+    Nine = 0x19,
+    Return = 0x24,
+    LeftArrow = 0x7B,
+    RightArrow = 0x7C,
+    DownArrow = 0x7D,
+    UpArrow = 0x7E,
+}
+
+impl TryFrom<i64> for Keycode {
+    type Error = ();
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0x0B => Self::B,
+            0x08 => Self::C,
+            0x04 => Self::H,
+            0x09 => Self::V,
+            0x0D => Self::W,
+            0x1D => Self::Zero,
+            0x12 => Self::One,
+            0x13 => Self::Two,
+            0x14 => Self::Three,
+            0x15 => Self::Four,
+            0x17 => Self::Five,
+            0x16 => Self::Six,
+            0x1A => Self::Seven,
+            0x1C => Self::Eight,
+            0x19 => Self::Nine,
+            0x24 => Self::Return,
+            0x7B => Self::LeftArrow,
+            0x7C => Self::RightArrow,
+            0x7D => Self::DownArrow,
+            0x7E => Self::UpArrow,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// A keycode together with the exact set of modifiers held with it.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct HotKey {
+    modifiers: u8,
+    keycode: Keycode,
+}
+
+const CMD: u8 = Modifier::Command.bit();
+const CMD_CTRL: u8 = Modifier::Command.bit() | Modifier::Control.bit();
+const CMD_ALT: u8 = Modifier::Command.bit() | Modifier::Alt.bit();
+const CMD_ALT_SHIFT: u8 = CMD_ALT | Modifier::Shift.bit();
+
+impl HotKey {
+    pub const RESIZE_LEFT: Self = Self::with(CMD_CTRL, Keycode::LeftArrow);
+    pub const RESIZE_RIGHT: Self = Self::with(CMD_CTRL, Keycode::RightArrow);
+    pub const RESIZE_UP: Self = Self::with(CMD_CTRL, Keycode::UpArrow);
+    pub const RESIZE_DOWN: Self = Self::with(CMD_CTRL, Keycode::DownArrow);
+
+    pub const OPEN_TERMINAL: Self = Self::with(CMD, Keycode::Return);
+
+    pub const FOCUS_LEFT: Self = Self::with(CMD_ALT, Keycode::LeftArrow);
+    pub const FOCUS_RIGHT: Self = Self::with(CMD_ALT, Keycode::RightArrow);
+    pub const FOCUS_UP: Self = Self::with(CMD_ALT, Keycode::UpArrow);
+    pub const FOCUS_DOWN: Self = Self::with(CMD_ALT, Keycode::DownArrow);
+
+    pub const MOVE_WINDOW_LEFT: Self = Self::with(CMD_ALT_SHIFT, Keycode::LeftArrow);
+    pub const MOVE_WINDOW_RIGHT: Self = Self::with(CMD_ALT_SHIFT, Keycode::RightArrow);
+    pub const MOVE_WINDOW_UP: Self = Self::with(CMD_ALT_SHIFT, Keycode::UpArrow);
+    pub const MOVE_WINDOW_DOWN: Self = Self::with(CMD_ALT_SHIFT, Keycode::DownArrow);
+
+    pub const FOCUS_DISPLAY_0: Self = Self::with(CMD_ALT, Keycode::Zero);
+    pub const FOCUS_DISPLAY_1: Self = Self::with(CMD_ALT, Keycode::One);
+    pub const FOCUS_DISPLAY_2: Self = Self::with(CMD_ALT, Keycode::Two);
+    pub const FOCUS_DISPLAY_3: Self = Self::with(CMD_ALT, Keycode::Three);
+    pub const FOCUS_DISPLAY_4: Self = Self::with(CMD_ALT, Keycode::Four);
+    pub const FOCUS_DISPLAY_5: Self = Self::with(CMD_ALT, Keycode::Five);
+    pub const FOCUS_DISPLAY_6: Self = Self::with(CMD_ALT, Keycode::Six);
+    pub const FOCUS_DISPLAY_7: Self = Self::with(CMD_ALT, Keycode::Seven);
+    pub const FOCUS_DISPLAY_8: Self = Self::with(CMD_ALT, Keycode::Eight);
+    pub const FOCUS_DISPLAY_9: Self = Self::with(CMD_ALT, Keycode::Nine);
+
+    pub const MOVE_TO_DISPLAY_0: Self = Self::with(CMD_ALT_SHIFT, Keycode::Zero);
+    pub const MOVE_TO_DISPLAY_1: Self = Self::with(CMD_ALT_SHIFT, Keycode::One);
+    pub const MOVE_TO_DISPLAY_2: Self = Self::with(CMD_ALT_SHIFT, Keycode::Two);
+    pub const MOVE_TO_DISPLAY_3: Self = Self::with(CMD_ALT_SHIFT, Keycode::Three);
+    pub const MOVE_TO_DISPLAY_4: Self = Self::with(CMD_ALT_SHIFT, Keycode::Four);
+    pub const MOVE_TO_DISPLAY_5: Self = Self::with(CMD_ALT_SHIFT, Keycode::Five);
+    pub const MOVE_TO_DISPLAY_6: Self = Self::with(CMD_ALT_SHIFT, Keycode::Six);
+    pub const MOVE_TO_DISPLAY_7: Self = Self::with(CMD_ALT_SHIFT, Keycode::Seven);
+    pub const MOVE_TO_DISPLAY_8: Self = Self::with(CMD_ALT_SHIFT, Keycode::Eight);
+    pub const MOVE_TO_DISPLAY_9: Self = Self::with(CMD_ALT_SHIFT, Keycode::Nine);
+
+    pub const VERTICAL_SPLIT: Self = Self::with(CMD_ALT, Keycode::V);
+    pub const HORIZONTAL_SPLIT: Self = Self::with(CMD_ALT, Keycode::H);
+
+    pub const TOGGLE_TABBED: Self = Self::with(CMD_ALT, Keycode::W);
+
+    pub const TOGGLE_FLOATING: Self = Self::with(CMD_ALT, Keycode::C);
+
+    pub const BALANCE: Self = Self::with(CMD_ALT, Keycode::B);
+
+    const fn with(modifiers: u8, keycode: Keycode) -> Self {
+        Self { modifiers, keycode }
+    }
+
+    pub fn new(modifiers: &[Modifier], keycode: Keycode) -> Self {
+        let modifiers = modifiers.iter().fold(0, |bits, m| bits | m.bit());
+        Self::with(modifiers, keycode)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyCommand {
+    NewTerminal,
+    Focus(Direction),
+    FocusDisplay(usize),
+    MoveWindowToDisplay(u8),
+    ToggleVerticalSplit,
+    ToggleHorizontalSplit,
+    ResizeWindow(Direction),
+    MoveWindow(Direction),
+    /// Switch the focused window's split between tiled and tabbed.
+    ToggleTabbed,
+    ToggleFloating,
+    /// Reset the focused window's split, and every split inside it, to equal
+    /// shares.
+    Balance,
+}
@@ -83,6 +83,18 @@ impl Window {
         self.owner_pid
     }
 
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn owner_name(&self) -> Option<&str> {
+        self.owner_name.as_deref()
+    }
+
+    pub fn layer(&self) -> i32 {
+        self.layer
+    }
+
     pub fn all_windows() -> Result<Vec<Window>> {
         let array_ref = unsafe {
             CGWindowListCopyWindowInfo(WindowListOption::EXCLUDE_DESKTOP_ELEMENTS, WindowId::NULL)
@@ -1,9 +1,18 @@
-use main::ctl::{CTL_SOCK, CtlToWmMessage, WmToCtlMessage};
+use main::criteria::Criteria;
+use main::ctl::{
+    BalanceArg, CTL_SOCK, Command, CtlToWmMessage, Dimension, DirectionArg, FocusArg,
+    FullscreenArg, LayoutArg, MarkArg, MoveArg, PositionArg, ResizeAmount, ResizeArg,
+    ScratchpadArg, StickyArg, WmToCtlMessage, WorkspaceArg,
+};
+use std::env::Args;
 use std::io::Write;
+use std::iter::Peekable;
 use std::os::unix::net::UnixStream;
 
 enum Mode {
     GetConfig,
+    GetMarks,
+    Command(Command),
 }
 
 enum OutputFormat {
@@ -15,6 +24,8 @@ impl Mode {
     pub fn run(&self) -> Result<(), String> {
         let (msg, exp_resp) = match self {
             Mode::GetConfig => (CtlToWmMessage::GetConfig, true),
+            Mode::GetMarks => (CtlToWmMessage::GetMarks, true),
+            Mode::Command(command) => (CtlToWmMessage::Command(command.clone()), true),
         };
 
         let mut tx = Vec::with_capacity(20);
@@ -43,7 +54,7 @@ impl Mode {
 }
 
 fn main() {
-    let mut args = std::env::args();
+    let mut args = std::env::args().peekable();
     let cmd = args.next().unwrap();
 
     let mut mode = None;
@@ -52,8 +63,181 @@ fn main() {
         match arg.as_str() {
             "get" => match args.next().unwrap().as_str() {
                 "config" => mode = Some(Mode::GetConfig),
+                "marks" => mode = Some(Mode::GetMarks),
                 _ => continue,
             },
+            "layout" => match args.next().unwrap().as_str() {
+                "tiled" => mode = Some(Mode::Command(Command::Layout(LayoutArg::Tiled))),
+                "tabbed" => mode = Some(Mode::Command(Command::Layout(LayoutArg::Tabbed))),
+                "stacking" => mode = Some(Mode::Command(Command::Layout(LayoutArg::Stacking))),
+                "toggle" if args.peek().is_some_and(|a| a == "split") => {
+                    args.next();
+                    mode = Some(Mode::Command(Command::Layout(LayoutArg::ToggleSplit)))
+                }
+                "toggle" => mode = Some(Mode::Command(Command::Layout(LayoutArg::Toggle))),
+                _ => continue,
+            },
+            "focus" => match args.next().unwrap().as_str() {
+                "parent" => mode = Some(Mode::Command(Command::Focus(FocusArg::Parent))),
+                "child" => mode = Some(Mode::Command(Command::Focus(FocusArg::Child))),
+                "last" => mode = Some(Mode::Command(Command::FocusLast)),
+                _ => continue,
+            },
+            "balance" => {
+                let arg = if args.peek().is_some_and(|a| a == "display") {
+                    args.next();
+                    BalanceArg::Display
+                } else {
+                    BalanceArg::Container
+                };
+                mode = Some(Mode::Command(Command::Balance(arg)))
+            }
+            "resize" => {
+                let arg = match args.next().unwrap().as_str() {
+                    verb @ ("grow" | "shrink") => {
+                        let dimension = match args.next().unwrap().as_str() {
+                            "width" => Dimension::Width,
+                            "height" => Dimension::Height,
+                            _ => continue,
+                        };
+                        let amount = parse_resize_amount(&mut args);
+
+                        if verb == "grow" {
+                            ResizeArg::Grow(dimension, amount)
+                        } else {
+                            ResizeArg::Shrink(dimension, amount)
+                        }
+                    }
+                    "set" => {
+                        if args.peek().is_some_and(|a| a == "width") {
+                            args.next();
+                        }
+                        let width = parse_set_length(&mut args);
+
+                        if args.peek().is_some_and(|a| a == "height") {
+                            args.next();
+                        }
+                        let height = parse_set_length(&mut args);
+
+                        ResizeArg::Set { width, height }
+                    }
+                    _ => continue,
+                };
+                mode = Some(Mode::Command(Command::Resize(arg)))
+            }
+            "move" => {
+                if args
+                    .peek()
+                    .is_some_and(|a| a == "window" || a == "container")
+                {
+                    args.next();
+                }
+                if args.peek().is_some_and(|a| a == "to") {
+                    args.next();
+                }
+
+                match args.next().unwrap().as_str() {
+                    "scratchpad" => {
+                        mode = Some(Mode::Command(Command::Scratchpad(ScratchpadArg::Move)))
+                    }
+                    "mark" => {
+                        let Some(name) = args.next() else { continue };
+                        mode = Some(Mode::Command(Command::MoveToMark(name)))
+                    }
+                    "position" => {
+                        let arg = match args.next().as_deref() {
+                            Some("center" | "centre") => PositionArg::Centre,
+                            Some(edge) => {
+                                let Some(edge) = parse_direction(edge) else {
+                                    continue;
+                                };
+                                if args.peek().is_some_and(|a| a == "half") {
+                                    args.next();
+                                    PositionArg::Half(edge)
+                                } else {
+                                    PositionArg::Edge(edge)
+                                }
+                            }
+                            None => continue,
+                        };
+                        mode = Some(Mode::Command(Command::MovePosition(arg)))
+                    }
+                    direction => {
+                        let Some(direction) = parse_direction(direction) else {
+                            continue;
+                        };
+                        let px = args
+                            .next_if(|a| a.parse::<f64>().is_ok())
+                            .map(|a| a.parse().unwrap());
+                        if px.is_some() && args.peek().is_some_and(|a| a == "px") {
+                            args.next();
+                        }
+                        mode = Some(Mode::Command(Command::Move(MoveArg { direction, px })))
+                    }
+                }
+            }
+            "mark" => {
+                let add = args.peek().is_some_and(|a| a == "--add");
+                if add || args.peek().is_some_and(|a| a == "--replace") {
+                    args.next();
+                }
+                let Some(name) = args.next() else { continue };
+                mode = Some(Mode::Command(Command::Mark(MarkArg { name, add })))
+            }
+            "sticky" => {
+                let arg = match args.next().as_deref() {
+                    Some("enable") => StickyArg::Enable,
+                    Some("disable") => StickyArg::Disable,
+                    Some("toggle") => StickyArg::Toggle,
+                    _ => continue,
+                };
+                mode = Some(Mode::Command(Command::Sticky(arg)))
+            }
+            "undo" => mode = Some(Mode::Command(Command::Undo)),
+            "redo" => mode = Some(Mode::Command(Command::Redo)),
+            "unmark" => mode = Some(Mode::Command(Command::Unmark(args.next()))),
+            criteria if criteria.starts_with('[') => {
+                let Ok(criteria) = Criteria::parse(&join_criteria(criteria, &mut args)) else {
+                    continue;
+                };
+                match args.next().as_deref() {
+                    Some("focus") => {
+                        mode = Some(Mode::Command(Command::FocusMatching(Box::new(criteria))))
+                    }
+                    _ => continue,
+                }
+            }
+            "workspace" => {
+                let arg = match args.next().unwrap().as_str() {
+                    "back_and_forth" => WorkspaceArg::BackAndForth,
+                    "number" => match args.next().and_then(|n| n.parse().ok()) {
+                        Some(n) => WorkspaceArg::Number(n),
+                        None => continue,
+                    },
+                    n => match n.parse() {
+                        Ok(n) => WorkspaceArg::Number(n),
+                        Err(_) => continue,
+                    },
+                };
+                mode = Some(Mode::Command(Command::Workspace(arg)))
+            }
+            "scratchpad" => match args.next().unwrap().as_str() {
+                "show" => mode = Some(Mode::Command(Command::Scratchpad(ScratchpadArg::Show))),
+                _ => continue,
+            },
+            "fullscreen" => {
+                let arg = match args.peek().map(String::as_str) {
+                    Some("enable") => FullscreenArg::Enable,
+                    Some("disable") => FullscreenArg::Disable,
+                    Some("toggle") => FullscreenArg::Toggle,
+                    _ => {
+                        mode = Some(Mode::Command(Command::Fullscreen(FullscreenArg::Toggle)));
+                        continue;
+                    }
+                };
+                args.next();
+                mode = Some(Mode::Command(Command::Fullscreen(arg)))
+            }
             _ => continue,
         }
     }
@@ -68,3 +252,64 @@ fn main() {
         std::process::exit(1);
     }
 }
+
+/// Parse an amount in any of the forms `<n> [px]`, `<m> ppt`, or
+/// `<n> px or <m> ppt`, leaving the arguments after it untouched.
+fn parse_resize_amount(args: &mut Peekable<Args>) -> ResizeAmount {
+    let mut amount = ResizeAmount::default();
+
+    while let Some(value) = args.peek().and_then(|a| a.parse::<f64>().ok()) {
+        args.next();
+        match args.peek().map(String::as_str) {
+            Some("ppt") => {
+                args.next();
+                amount.ppt = Some(value);
+            }
+            Some("px") => {
+                args.next();
+                amount.px = Some(value);
+            }
+            _ => amount.px = Some(value),
+        }
+
+        if args.peek().is_some_and(|a| a == "or") {
+            args.next();
+        } else {
+            break;
+        }
+    }
+
+    amount
+}
+
+/// Parse one dimension of `resize set`, where a missing or zero length leaves
+/// that dimension unchanged.
+fn parse_set_length(args: &mut Peekable<Args>) -> Option<ResizeAmount> {
+    let amount = parse_resize_amount(args);
+    let length = amount.ppt.or(amount.px)?;
+    (length != 0.0).then_some(amount)
+}
+
+/// Parse a direction, accepting `top` and `bottom` for `up` and `down`.
+fn parse_direction(s: &str) -> Option<DirectionArg> {
+    match s {
+        "left" => Some(DirectionArg::Left),
+        "right" => Some(DirectionArg::Right),
+        "up" | "top" => Some(DirectionArg::Up),
+        "down" | "bottom" => Some(DirectionArg::Down),
+        _ => None,
+    }
+}
+
+/// Join criteria given unquoted, and so split across arguments, back together
+/// up to the argument closing them.
+fn join_criteria(first: &str, args: &mut Peekable<Args>) -> String {
+    let mut criteria = first.to_string();
+    while !criteria.ends_with(']')
+        && let Some(arg) = args.next()
+    {
+        criteria.push(' ');
+        criteria.push_str(&arg);
+    }
+    criteria
+}
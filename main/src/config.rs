@@ -1,15 +1,47 @@
 use crate::log::Level;
+use crate::rules::Rule;
 use serde::{Deserialize, Serialize};
 
-#[derive(Default, Copy, Clone, Debug, Serialize, Deserialize)]
+// Arbitrary reasonable constant that stop windows getting too
+// small. When this value is too small, the OS doesn't let the
+// smaller window get smaller, but this code will make the larger
+// window get larger and thus they overlap.
+pub const DEFAULT_MIN_WINDOW_SIZE: f64 = 200.0;
+pub const DEFAULT_RESIZE_AMOUNT: f64 = 50.0;
+pub const DEFAULT_MOVE_AMOUNT: f64 = 50.0;
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
     pub window_padding: Option<f64>,
     pub log_level: Level,
+    /// How far, in pixels, a resize without an explicit amount moves a
+    /// divider.
+    pub resize_amount: Option<f64>,
+    /// How far, in pixels, a move without an explicit amount moves a floating
+    /// window.
+    pub move_amount: Option<f64>,
+    /// The smallest, in pixels, a resize may shrink a window to.
+    pub min_window_size: Option<f64>,
+    /// Whether shifting focus past the last display in some direction wraps
+    /// around to the display at the opposite edge.
+    pub focus_wrapping: bool,
+    /// Whether switching to the logical display already shown goes back to
+    /// the one shown before it.
+    pub workspace_auto_back_and_forth: bool,
+    /// Rules applied to windows as they first appear, in the order given. The
+    /// built-in `Rule::defaults` come first unless `--no-default-rules` is
+    /// given.
+    pub rules: Vec<Rule>,
+    /// Whether a window assigned to a logical display other than the active
+    /// one takes focus there as it appears, rather than leaving focus where it
+    /// is.
+    pub focus_assigned_windows: bool,
 }
 
 impl Config {
     pub fn must_parse() -> Self {
         let mut ret = Self::default();
+        let mut default_rules = true;
         let mut args = std::env::args().skip(1);
 
         while let Some(arg) = args.next() {
@@ -22,6 +54,48 @@ impl Config {
                         .expect("expected a usize value after --padding");
                     ret.window_padding = Some(padding as f64);
                 }
+                "--resize-amount" => {
+                    let amount = args
+                        .next()
+                        .expect("expected a usize value after --resize-amount")
+                        .parse::<usize>()
+                        .expect("expected a usize value after --resize-amount");
+                    ret.resize_amount = Some(amount as f64);
+                }
+                "--move-amount" => {
+                    let amount = args
+                        .next()
+                        .expect("expected a usize value after --move-amount")
+                        .parse::<usize>()
+                        .expect("expected a usize value after --move-amount");
+                    ret.move_amount = Some(amount as f64);
+                }
+                "--min-window-size" => {
+                    let size = args
+                        .next()
+                        .expect("expected a usize value after --min-window-size")
+                        .parse::<usize>()
+                        .expect("expected a usize value after --min-window-size");
+                    ret.min_window_size = Some(size as f64);
+                }
+                "--focus-wrapping" => ret.focus_wrapping = true,
+                "--workspace-auto-back-and-forth" => ret.workspace_auto_back_and_forth = true,
+                "--for-window" => {
+                    let rule = args.next().expect("expected a rule after --for-window");
+                    match Rule::parse(&rule) {
+                        Ok(rule) => ret.rules.push(rule),
+                        Err(e) => panic!("invalid rule after --for-window: {e}"),
+                    }
+                }
+                "--assign" => {
+                    let assignment = args.next().expect("expected an assignment after --assign");
+                    match Rule::parse_assign(&assignment) {
+                        Ok(rule) => ret.rules.push(rule),
+                        Err(e) => panic!("invalid assignment after --assign: {e}"),
+                    }
+                }
+                "--no-default-rules" => default_rules = false,
+                "--focus-assigned-windows" => ret.focus_assigned_windows = true,
                 "--log-level" => {
                     let level: Level = args
                         .next()
@@ -37,6 +111,10 @@ impl Config {
             }
         }
 
+        if default_rules {
+            ret.rules.splice(0..0, Rule::defaults());
+        }
+
         ret
     }
 }
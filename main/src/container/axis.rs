@@ -1,4 +1,4 @@
-use core_graphics::Direction;
+use core_graphics::{Bounds, Direction};
 use std::fmt::Display;
 
 #[derive(Debug, Default, Copy, Clone, Hash, PartialEq)]
@@ -18,13 +18,29 @@ impl Display for Axis {
 }
 
 impl Axis {
-    pub(crate) fn can_resize_in_direction(&self, direction: Direction) -> bool {
-        matches!(
-            (self, direction),
-            (Axis::Horizontal, Direction::Left)
-                | (Axis::Horizontal, Direction::Right)
-                | (Axis::Vertical, Direction::Up)
-                | (Axis::Vertical, Direction::Down)
-        )
+    /// The length of `bounds` along the axis.
+    pub fn length_of(self, bounds: Bounds) -> f64 {
+        match self {
+            Self::Horizontal => bounds.width,
+            Self::Vertical => bounds.height,
+        }
+    }
+
+    /// The axis at right angles to this one.
+    pub fn perpendicular(self) -> Self {
+        match self {
+            Self::Vertical => Self::Horizontal,
+            Self::Horizontal => Self::Vertical,
+        }
+    }
+}
+
+impl From<Direction> for Axis {
+    /// The axis a movement in `direction` travels along.
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Left | Direction::Right => Self::Horizontal,
+            Direction::Up | Direction::Down => Self::Vertical,
+        }
     }
 }
@@ -0,0 +1,104 @@
+use crate::container::Axis;
+use core_graphics::{Bounds, Direction};
+
+/// How far two edges may overlap, or two spans may share, and still count as
+/// merely touching, to absorb rounding in laid out bounds.
+const EDGE_TOLERANCE: f64 = 1.0;
+
+/// Returns the candidate closest to `from` in `direction`, out of those that
+/// lie wholly beyond `from` in that direction and overlap it on the
+/// perpendicular axis. Ties go to the candidate sharing the most of `from`'s
+/// span, then to the topmost or leftmost.
+pub fn nearest_in_direction<T>(
+    from: Bounds,
+    candidates: impl IntoIterator<Item = (T, Bounds)>,
+    direction: Direction,
+) -> Option<T> {
+    candidates_towards(from, candidates, direction)
+        .min_by(|a, b| {
+            a.gap
+                .total_cmp(&b.gap)
+                .then(b.overlap.total_cmp(&a.overlap))
+                .then(a.start.total_cmp(&b.start))
+        })
+        .map(|c| c.item)
+}
+
+/// Returns the candidate furthest from `from` in `direction`, out of those
+/// that lie wholly beyond `from` in that direction and overlap it on the
+/// perpendicular axis, with ties broken as in `nearest_in_direction`.
+pub fn furthest_in_direction<T>(
+    from: Bounds,
+    candidates: impl IntoIterator<Item = (T, Bounds)>,
+    direction: Direction,
+) -> Option<T> {
+    candidates_towards(from, candidates, direction)
+        .min_by(|a, b| {
+            b.gap
+                .total_cmp(&a.gap)
+                .then(b.overlap.total_cmp(&a.overlap))
+                .then(a.start.total_cmp(&b.start))
+        })
+        .map(|c| c.item)
+}
+
+/// Whether `to` lies wholly beyond `from` in `direction` and overlaps it on
+/// the perpendicular axis.
+pub fn lies_in_direction(from: Bounds, to: Bounds, direction: Direction) -> bool {
+    gap_towards(from, to, direction).is_some()
+}
+
+struct Candidate<T> {
+    item: T,
+    gap: f64,
+    overlap: f64,
+    /// Where the candidate starts on the perpendicular axis.
+    start: f64,
+}
+
+fn candidates_towards<T>(
+    from: Bounds,
+    candidates: impl IntoIterator<Item = (T, Bounds)>,
+    direction: Direction,
+) -> impl Iterator<Item = Candidate<T>> {
+    candidates.into_iter().filter_map(move |(item, to)| {
+        Some(Candidate {
+            item,
+            gap: gap_towards(from, to, direction)?,
+            overlap: perpendicular_overlap(from, to, direction),
+            start: match Axis::from(direction) {
+                Axis::Horizontal => to.y,
+                Axis::Vertical => to.x,
+            },
+        })
+    })
+}
+
+/// The distance from the edge of `from` facing `direction` to the facing edge
+/// of `to`, provided `to` lies beyond `from` in `direction` and overlaps it on
+/// the perpendicular axis.
+fn gap_towards(from: Bounds, to: Bounds, direction: Direction) -> Option<f64> {
+    if perpendicular_overlap(from, to, direction) < EDGE_TOLERANCE {
+        return None;
+    }
+
+    let gap = match direction {
+        Direction::Left => from.x - (to.x + to.width),
+        Direction::Right => to.x - (from.x + from.width),
+        Direction::Up => from.y - (to.y + to.height),
+        Direction::Down => to.y - (from.y + from.height),
+    };
+
+    (gap > -EDGE_TOLERANCE).then_some(gap.max(0.0))
+}
+
+/// How much of `from` and `to` share on the axis perpendicular to
+/// `direction`.
+fn perpendicular_overlap(from: Bounds, to: Bounds, direction: Direction) -> f64 {
+    let (from_start, from_len, to_start, to_len) = match Axis::from(direction) {
+        Axis::Horizontal => (from.y, from.height, to.y, to.height),
+        Axis::Vertical => (from.x, from.width, to.x, to.width),
+    };
+
+    (from_start + from_len).min(to_start + to_len) - from_start.max(to_start)
+}
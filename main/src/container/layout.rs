@@ -0,0 +1,80 @@
+use crate::ctl::LayoutArg;
+use std::fmt::Display;
+
+/// How a split container arranges its children within its bounds.
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq)]
+pub enum Layout {
+    /// Children are placed side by side along the split's axis.
+    #[default]
+    Tiled,
+    /// Every child is given the whole of the split's bounds, and only the
+    /// focused child is visible; the rest are stacked behind it.
+    Tabbed,
+    /// Every child shares one region below a column of title strips, one strip
+    /// per child, and only the focused child is visible.
+    Stacked,
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tiled => write!(f, "Tiled"),
+            Self::Tabbed => write!(f, "Tabbed"),
+            Self::Stacked => write!(f, "Stacked"),
+        }
+    }
+}
+
+impl Layout {
+    /// The layout that follows this one when cycling with a toggle, matching
+    /// i3's `layout toggle` order.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Tiled => Self::Stacked,
+            Self::Stacked => Self::Tabbed,
+            Self::Tabbed => Self::Tiled,
+        }
+    }
+
+    /// Whether only one child of a split with this layout is visible at once.
+    pub fn hides_children(self) -> bool {
+        matches!(self, Self::Tabbed | Self::Stacked)
+    }
+}
+
+/// A requested change to a split's layout.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LayoutChange {
+    Set(Layout),
+    Toggle,
+    /// Flip a tiled split between horizontal and vertical, or return a tabbed
+    /// or stacked split to tiling along its existing axis.
+    ToggleSplit,
+    /// Switch between tabbed and tiled, as the tabbed hotkey does.
+    ToggleTabbed,
+}
+
+impl LayoutChange {
+    /// Resolve the change against the layout a split currently has.
+    pub fn apply(self, current: Layout) -> Layout {
+        match self {
+            Self::Set(layout) => layout,
+            Self::Toggle => current.next(),
+            Self::ToggleSplit => Layout::Tiled,
+            Self::ToggleTabbed if current == Layout::Tabbed => Layout::Tiled,
+            Self::ToggleTabbed => Layout::Tabbed,
+        }
+    }
+}
+
+impl From<LayoutArg> for LayoutChange {
+    fn from(arg: LayoutArg) -> Self {
+        match arg {
+            LayoutArg::Tiled => Self::Set(Layout::Tiled),
+            LayoutArg::Tabbed => Self::Set(Layout::Tabbed),
+            LayoutArg::Stacking => Self::Set(Layout::Stacked),
+            LayoutArg::Toggle => Self::Toggle,
+            LayoutArg::ToggleSplit => Self::ToggleSplit,
+        }
+    }
+}
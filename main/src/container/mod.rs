@@ -1,9 +1,18 @@
 mod axis;
+mod geometry;
+mod layout;
 pub(crate) mod leaf;
+mod resize;
 pub(crate) mod split;
+#[cfg(test)]
+mod tests;
 
 pub use crate::container::axis::Axis;
+use crate::container::geometry::lies_in_direction;
+pub use crate::container::geometry::{furthest_in_direction, nearest_in_direction};
+pub use crate::container::layout::{Layout, LayoutChange};
 use crate::container::leaf::Leaf;
+pub use crate::container::resize::{Length, Resize};
 use crate::container::split::Split;
 use crate::error::{Error, Result};
 use core_graphics::{Bounds, Direction, WindowId};
@@ -15,6 +24,29 @@ pub enum RemoveResult {
     NotFound,
 }
 
+#[derive(Debug, PartialEq)]
+pub enum MoveResult {
+    /// The window was moved within the container.
+    Moved,
+    /// The window is at the edge of the container in the direction of the
+    /// move, and so can only be moved by an ancestor of the container.
+    AtEdge,
+}
+
+/// The height of each title strip reserved at the top of a stacked split.
+pub const TITLE_STRIP_HEIGHT: f64 = 20.0;
+
+/// A title strip belonging to one child of a stacked split.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct TitleStrip {
+    pub bounds: Bounds,
+    /// The window the strip represents; for a nested split this is the window
+    /// that would take focus when the strip's child is shown.
+    pub window: WindowId,
+    /// Whether the strip's child is the one currently shown.
+    pub is_active: bool,
+}
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Window {
     pub id: WindowId,
@@ -33,6 +65,26 @@ impl From<crate::window::Window> for Window {
     }
 }
 
+/// Where a window sat in a container tree, so that it can be put back there
+/// after leaving it, as when a tiled window is floated and later tiled again.
+/// The window's parent split is found again through the windows that shared
+/// it, so the position survives other changes to the tree in between.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreePosition {
+    /// The window, with the minimum size it had in the tree.
+    pub window: Window,
+    /// Every other window under the window's parent split.
+    siblings: HashSet<WindowId>,
+    /// The axis and layout of the window's parent split.
+    axis: Axis,
+    layout: Layout,
+    /// A window under the child just before the window in its parent split,
+    /// or `None` if the window was the split's first child.
+    previous: Option<WindowId>,
+    /// The window's weight as a share of its parent split's total weight.
+    share: f64,
+}
+
 #[cfg(test)]
 impl Window {
     pub fn dummy(id: WindowId) -> Self {
@@ -44,7 +96,7 @@ impl Window {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub(super) enum Container {
     Leaf(Leaf),
@@ -66,6 +118,14 @@ impl Container {
         }
     }
 
+    /// Return the container's minimum length along `axis`.
+    pub(super) fn min_length(&self, axis: Axis) -> f64 {
+        match axis {
+            Axis::Horizontal => self.min_width(),
+            Axis::Vertical => self.min_height(),
+        }
+    }
+
     // Rules for adding a window to a container:
     //  - If a container is empty, create a default split and add the new window
     //    as a single child.
@@ -90,6 +150,207 @@ impl Container {
         }
     }
 
+    /// Add `window` to the split holding `target`, then lay the whole tree out
+    /// again, so that the rest of the tree can make room when the split itself
+    /// is too small for the window's minimum size. Errors if the tree's minimum
+    /// size no longer fits in its bounds.
+    pub fn add_window_beside(
+        &mut self,
+        target: WindowId,
+        window: Window,
+        padding: f64,
+    ) -> Result<()> {
+        let Some(Self::Split(parent)) = self.get_parent_of_window_mut(target) else {
+            return Err(Error::CannotFindWindow);
+        };
+        parent.push_window(window, padding);
+
+        let Self::Split(root) = self else {
+            return Err(Error::ExpectedSplitContainer);
+        };
+
+        if !root.fits() {
+            let Some(Self::Split(parent)) = self.get_parent_of_window_mut(window.id) else {
+                unreachable!("the window was just added to a split");
+            };
+            parent.children.pop();
+            parent.weights.pop();
+            return Err(Error::CannotFitWindow);
+        }
+
+        root.relayout_children(padding)
+    }
+
+    /// Return where `target` sits in the tree, for `restore_window` to put it
+    /// back there once it has been removed.
+    pub fn position_of_window(&self, target: WindowId) -> Option<TreePosition> {
+        let parent = self.parent_of_window(target)?;
+        let i = parent
+            .children
+            .iter()
+            .position(|child| matches!(child, Self::Leaf(leaf) if leaf.window.id == target))?;
+        let Self::Leaf(leaf) = &parent.children[i] else {
+            unreachable!("the child was just found to be a leaf");
+        };
+
+        let mut siblings = parent.window_ids();
+        siblings.remove(&target);
+
+        Some(TreePosition {
+            window: leaf.window,
+            siblings,
+            axis: parent.axis,
+            layout: parent.layout,
+            previous: i.checked_sub(1).map(|p| parent.children[p].focus_target()),
+            share: parent.weights[i] / parent.weights.iter().sum::<f64>(),
+        })
+    }
+
+    // To put a window back where `position` says it sat:
+    //  1. Find the innermost split holding every one of its old siblings still
+    //     in the tree. That is its old parent, unless the parent was collapsed
+    //     when the window left, in which case it is the split the parent's
+    //     remaining child was moved into. A lone remaining sibling is wrapped
+    //     in a new split with the old parent's axis and layout, standing in
+    //     for the old parent.
+    //  2. Insert the window just after the child holding the window that came
+    //     before it, or first if there was none or it has since left.
+    //  3. Weight the window so that it takes the same share of the split as it
+    //     had before.
+    //  4. If the tree's minimum size no longer fits in its bounds, take the
+    //     window back out and error, as `add_window_beside` does.
+    // Errors with `CannotFindWindow` if none of the old siblings remain, so
+    // that the caller can add the window somewhere else instead.
+    pub fn restore_window(&mut self, position: &TreePosition, padding: f64) -> Result<()> {
+        let siblings: Vec<WindowId> = position
+            .siblings
+            .iter()
+            .copied()
+            .filter(|&sibling| self.contains_window(sibling))
+            .collect();
+        if siblings.is_empty() {
+            return Err(Error::CannotFindWindow);
+        }
+
+        let mut parent = self
+            .innermost_split_holding_mut(&siblings)
+            .ok_or(Error::ExpectedSplitContainer)?;
+        if let [sibling] = siblings[..]
+            && (parent.axis, parent.layout) != (position.axis, position.layout)
+        {
+            // Safety: the parent was found to hold the sibling.
+            let i = parent
+                .children
+                .iter()
+                .position(|child| child.contains_window(sibling))
+                .unwrap();
+            let child = &mut parent.children[i];
+            child.wrap(position.axis);
+            let Self::Split(wrapper) = child else {
+                unreachable!("the child was just wrapped in a split");
+            };
+            wrapper.layout = position.layout;
+            parent = wrapper;
+        }
+        let index = position
+            .previous
+            .and_then(|previous| {
+                parent
+                    .children
+                    .iter()
+                    .position(|child| child.contains_window(previous))
+            })
+            .map_or(0, |i| i + 1);
+        let total: f64 = parent.weights.iter().sum();
+        let weight = if position.share < 1.0 {
+            total * position.share / (1.0 - position.share)
+        } else {
+            total / parent.weights.len() as f64
+        };
+
+        let window = position.window;
+        parent
+            .children
+            .insert(index, Self::Leaf(Leaf::new(parent.bounds, padding, window)));
+        parent.weights.insert(index, weight);
+
+        let Self::Split(root) = self else {
+            return Err(Error::ExpectedSplitContainer);
+        };
+
+        if !root.fits() {
+            let Some(Self::Split(parent)) = self.get_parent_of_window_mut(window.id) else {
+                unreachable!("the window was just added to a split");
+            };
+            parent.children.remove(index);
+            parent.weights.remove(index);
+            // Drop any split the sibling was wrapped in.
+            self.normalise(padding)?;
+            return Err(Error::CannotFitWindow);
+        }
+
+        root.relayout_children(padding)
+    }
+
+    /// Return the split that is the direct parent of the leaf managing
+    /// `target`.
+    fn parent_of_window(&self, target: WindowId) -> Option<&Split> {
+        let Self::Split(split) = self else {
+            return None;
+        };
+
+        if split
+            .children
+            .iter()
+            .any(|child| matches!(child, Self::Leaf(leaf) if leaf.window.id == target))
+        {
+            return Some(split);
+        }
+
+        split
+            .children
+            .iter()
+            .find_map(|child| child.parent_of_window(target))
+    }
+
+    /// Return the innermost split holding every one of `windows`, all of which
+    /// must lie below the container.
+    fn innermost_split_holding_mut(&mut self, windows: &[WindowId]) -> Option<&mut Split> {
+        let Self::Split(split) = self else {
+            return None;
+        };
+
+        let holding_child = split.children.iter().position(|child| {
+            matches!(child, Self::Split(_))
+                && windows.iter().all(|&window| child.contains_window(window))
+        });
+
+        match holding_child {
+            Some(i) => split.children[i].innermost_split_holding_mut(windows),
+            None => Some(split),
+        }
+    }
+
+    /// Split the leaf managing `target` along `axis`, in the style of i3's
+    /// `split`. A window sharing its split with others is wrapped in place in
+    /// a new nested split, while a lone window just changes its split's axis.
+    /// Either way, the next window added beside `target` lands in a split
+    /// along `axis`.
+    pub fn split_window(&mut self, target: WindowId, axis: Axis) -> Result<()> {
+        let Some(Self::Split(parent)) = self.get_parent_of_window_mut(target) else {
+            return Err(Error::CannotFindWindow);
+        };
+
+        if parent.children.len() < 2 {
+            return parent.split(axis);
+        }
+
+        // Safety: `target` was found as a direct child of `parent`.
+        self.parent_leaf_of_window_mut(target).unwrap().wrap(axis);
+
+        Ok(())
+    }
+
     pub(super) fn window_ids(&self) -> HashSet<WindowId> {
         match self {
             Self::Leaf(leaf) => HashSet::from([leaf.window.id]),
@@ -104,15 +365,53 @@ impl Container {
         }
     }
 
+    /// Remove the window `window_id` from the tree rooted at the container,
+    /// normalising what remains.
     pub(super) fn remove_window(
         &mut self,
         window_id: WindowId,
         padding: f64,
     ) -> Result<RemoveResult> {
+        let result = match self {
+            Self::Leaf(leaf) if leaf.window.id == window_id => RemoveResult::BecomeEmpty,
+            Self::Split(split) => split.remove_window(window_id, padding)?,
+            Self::Leaf(_) => RemoveResult::NotFound,
+        };
+
+        if matches!(result, RemoveResult::Removed) {
+            self.normalise(padding)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Bring the tree rooted at the container into its canonical form, in the
+    /// style of i3's tree flattening: single-child splits are collapsed into
+    /// their parent, and tiled splits nested directly inside a tiled split
+    /// along the same axis are merged into it. The root itself always stays a
+    /// split. Splitting a window is the one mutation that deliberately leaves
+    /// a single-child split behind, for the next window to be added to.
+    fn normalise(&mut self, padding: f64) -> Result<()> {
+        let Self::Split(split) = self else {
+            return Ok(());
+        };
+
+        split.flatten();
+        split.relayout_children(padding)?;
+
+        debug_assert!(self.is_normalised(), "container tree is not normalised");
+        Ok(())
+    }
+
+    /// Whether the tree rooted at the container is canonical; see
+    /// `Container::normalise`.
+    pub(super) fn is_normalised(&self) -> bool {
         match self {
-            Self::Leaf(leaf) if leaf.window.id == window_id => Ok(RemoveResult::BecomeEmpty),
-            Self::Split(split) => split.remove_window(window_id, padding),
-            Self::Leaf(_) => Ok(RemoveResult::NotFound),
+            Self::Leaf(_) => true,
+            Self::Split(split) => {
+                !matches!(split.children.as_slice(), [Self::Split(_)])
+                    && split.children_are_normalised()
+            }
         }
     }
 
@@ -167,16 +466,423 @@ impl Container {
         Ok(())
     }
 
-    pub fn resize_window(
+    /// Return how many levels above the leaf managing `target` lie between it
+    /// and the split nearest the selection -- the container `depth` levels
+    /// above that leaf -- for which `accepts` holds, given the index of the
+    /// split's child holding the selection.
+    fn nearest_ancestor(
+        &self,
+        target: WindowId,
+        depth: usize,
+        accepts: impl Fn(&Split, usize) -> bool,
+    ) -> Option<usize> {
+        let max_depth = self.depth_of_window(target)?;
+
+        (depth + 1..=max_depth).find(|&d| match self.selected(target, d) {
+            Some(Self::Split(split)) => split
+                .child_containing(target)
+                .is_ok_and(|(i, _)| accepts(split, i)),
+            _ => false,
+        })
+    }
+
+    /// Return the split nearest the selection that tiles along `axis`, and the
+    /// index of its child holding the selection.
+    fn resizable_ancestor_mut(
+        &mut self,
+        target: WindowId,
+        depth: usize,
+        axis: Axis,
+    ) -> Result<(&mut Split, usize)> {
+        let ancestor_depth = self
+            .nearest_ancestor(target, depth, |split, _| split.can_resize_along(axis))
+            .ok_or(Error::CannotResizeInDirection)?;
+
+        let Self::Split(root) = self else {
+            return Err(Error::ExpectedSplitContainer);
+        };
+        root.selection_parent_mut(target, ancestor_depth - 1)
+    }
+
+    // To resize the selected container -- the one `depth` levels above the
+    // leaf managing `window_id` -- in `direction`, in the style of i3:
+    //  - Find the nearest split above the selection that runs along
+    //    `direction`, and where the child holding the selection has a
+    //    neighbour on its `direction` side.
+    //  - Move the divider between them `amount` in `direction`, growing the
+    //    selection towards it, but never shrinking the neighbour below `min`.
+    pub fn resize_container(
         &mut self,
         window_id: WindowId,
+        depth: usize,
         direction: Direction,
+        amount: f64,
+        min: f64,
         padding: f64,
     ) -> Result<()> {
+        let ancestor_depth = self
+            .nearest_ancestor(window_id, depth, |split, i| {
+                split.neighbour_towards(i, direction).is_some()
+            })
+            .ok_or(Error::CannotResizeInDirection)?;
+
+        let Self::Split(root) = self else {
+            return Err(Error::ExpectedSplitContainer);
+        };
+        let (split, i) = root.selection_parent_mut(window_id, ancestor_depth - 1)?;
+
+        match direction {
+            Direction::Left | Direction::Up => split.move_divider(i - 1, -amount, min, padding),
+            Direction::Right | Direction::Down => split.move_divider(i, amount, min, padding),
+        }
+    }
+
+    /// Apply `resize` to the selected container -- the one `depth` levels
+    /// above the leaf managing `window_id` -- growing or shrinking it by `step`
+    /// pixels when no length is given, and never shrinking a container below
+    /// `min`. Lengths in percentage points are resolved against the nearest
+    /// split above the selection that tiles along the resized axis.
+    pub fn apply_resize(
+        &mut self,
+        window_id: WindowId,
+        depth: usize,
+        resize: Resize,
+        step: f64,
+        min: f64,
+        padding: f64,
+    ) -> Result<()> {
+        let (axis, by) = match resize {
+            Resize::Grow(axis, by) => (axis, by.unwrap_or(Length::Px(step))),
+            Resize::Shrink(axis, by) => (axis, -by.unwrap_or(Length::Px(step))),
+            Resize::Set { width, height } => {
+                for (axis, length) in [(Axis::Horizontal, width), (Axis::Vertical, height)] {
+                    let Some(length) = length else {
+                        continue;
+                    };
+
+                    let (split, i) = self.resizable_ancestor_mut(window_id, depth, axis)?;
+                    let target_length = length.resolve(axis.length_of(split.bounds));
+                    let delta = target_length - axis.length_of(split.children[i].bounds());
+                    split.grow_child(i, delta, min, padding)?;
+                }
+                return Ok(());
+            }
+        };
+
+        let (split, i) = self.resizable_ancestor_mut(window_id, depth, axis)?;
+        let delta = by.resolve(axis.length_of(split.bounds));
+        split.grow_child(i, delta, min, padding)
+    }
+
+    // To move the selected container -- the one `depth` levels above the leaf
+    // managing `target` -- within the tree, in the style of i3's `move`:
+    //  - Delegate to the root split; see `Split::move_container`.
+    //  - If no split along the selection's path can take it, the selection is
+    //    at the edge of the whole tree. If the root's orientation matches the
+    //    direction of the move, or the selection holds every window, there is
+    //    nowhere further to go; otherwise wrap the root in a new split of the
+    //    matching orientation with the selection placed on the requested side.
+    //  - Normalise the tree, as the move may have left a split behind with a
+    //    single child.
+    pub fn move_container(
+        &mut self,
+        target: WindowId,
+        depth: usize,
+        direction: Direction,
+        padding: f64,
+    ) -> Result<MoveResult> {
+        let selected_windows = self
+            .selected(target, depth)
+            .ok_or(Error::CannotFindSelection)?
+            .window_ids();
+
+        let Self::Split(split) = self else {
+            return Err(Error::ExpectedSplitContainer);
+        };
+
+        if selected_windows == split.window_ids() {
+            return Ok(MoveResult::AtEdge);
+        }
+
+        if split.move_container(target, depth, direction, padding)? == MoveResult::Moved {
+            self.normalise(padding)?;
+            return Ok(MoveResult::Moved);
+        }
+
+        let axis = Axis::from(direction);
+        if split.orientation() == axis {
+            return Ok(MoveResult::AtEdge);
+        }
+
+        let selected = split.take_container(target, depth, padding)?;
+        let bounds = split.bounds;
+        let old_root =
+            std::mem::replace(self, Self::Split(Split::new(bounds, axis, padding, vec![])));
+
+        let Self::Split(root) = self else {
+            unreachable!("the root was just replaced with a split");
+        };
+        root.children.push(old_root);
+        root.weights.push(1.0);
+
+        let index = match direction {
+            Direction::Left | Direction::Up => 0,
+            Direction::Right | Direction::Down => 1,
+        };
+        root.insert_child(index, selected, padding)?;
+
+        self.normalise(padding)?;
+        Ok(MoveResult::Moved)
+    }
+
+    /// Wrap the container in a new split along `axis`, as its only child.
+    pub fn wrap(&mut self, axis: Axis) {
+        // A placeholder that is immediately replaced, so that the container can
+        // be moved into its own wrapper.
+        let placeholder = Self::Split(Split::new(self.bounds(), axis, 0.0, vec![]));
+
+        *self = match std::mem::replace(self, placeholder) {
+            Self::Leaf(leaf) => Self::Split(leaf.split(axis)),
+            Self::Split(split) => Self::Split(split.wrap(axis)),
+        };
+    }
+
+    /// Return how many splits lie between the container and the leaf managing
+    /// `target`, or `None` if the container does not manage it.
+    pub fn depth_of_window(&self, target: WindowId) -> Option<usize> {
+        match self {
+            Self::Leaf(leaf) if leaf.window.id == target => Some(0),
+            Self::Leaf(_) => None,
+            Self::Split(split) => split
+                .children
+                .iter()
+                .find_map(|c| c.depth_of_window(target))
+                .map(|depth| depth + 1),
+        }
+    }
+
+    /// Return the container `depth` levels above the leaf managing `target`.
+    pub fn selected(&self, target: WindowId, depth: usize) -> Option<&Self> {
+        let own_depth = self.depth_of_window(target)?;
+        if own_depth == depth {
+            return Some(self);
+        }
+
+        match self {
+            Self::Split(split) if own_depth > depth => split
+                .children
+                .iter()
+                .find_map(|c| c.selected(target, depth)),
+            _ => None,
+        }
+    }
+
+    /// Return the container `depth` levels above the leaf managing `target`.
+    pub fn selected_mut(&mut self, target: WindowId, depth: usize) -> Option<&mut Self> {
+        let own_depth = self.depth_of_window(target)?;
+        if own_depth == depth {
+            return Some(self);
+        }
+
+        match self {
+            Self::Split(split) if own_depth > depth => split
+                .children
+                .iter_mut()
+                .find_map(|c| c.selected_mut(target, depth)),
+            _ => None,
+        }
+    }
+
+    /// Returns every window managed by the container, in tree order.
+    pub fn windows(&self) -> Vec<Window> {
+        match self {
+            Self::Leaf(leaf) => vec![leaf.window],
+            Self::Split(split) => split.children.iter().flat_map(|c| c.windows()).collect(),
+        }
+    }
+
+    /// Returns the window that should take focus when moving from `target` to
+    /// the neighbouring tab in `direction`, if `target` lives in a tabbed or
+    /// stacked split that has a tab on that side. Tabs are walked left and
+    /// right, stacks up and down. The innermost such split wins.
+    pub fn neighbouring_tab(&self, target: WindowId, direction: Direction) -> Option<WindowId> {
+        let Self::Split(split) = self else {
+            return None;
+        };
+
+        let i = split
+            .children
+            .iter()
+            .position(|c| c.contains_window(target))?;
+
+        if let Some(inner) = split.children[i].neighbouring_tab(target, direction) {
+            return Some(inner);
+        }
+
+        let j = match (split.layout, direction) {
+            (Layout::Tabbed, Direction::Left) | (Layout::Stacked, Direction::Up) => {
+                i.checked_sub(1)?
+            }
+            (Layout::Tabbed, Direction::Right) | (Layout::Stacked, Direction::Down) => i + 1,
+            _ => return None,
+        };
+
+        split.children.get(j).map(Container::focus_target)
+    }
+
+    /// Returns the bounds of every window that is not hidden behind another
+    /// tab. The visible child of a tabbed or stacked split is the one
+    /// containing `focused`, or its last focused child if `focused` lives
+    /// elsewhere.
+    pub fn visible_window_bounds(&self, focused: WindowId) -> HashMap<WindowId, Bounds> {
         match self {
-            // TODO: error
-            Self::Leaf(_) => Err(Error::WindowNotFound),
-            Self::Split(split) => split.resize_window(window_id, direction, padding),
+            Self::Leaf(leaf) => HashMap::from([(leaf.window.id, leaf.bounds)]),
+            Self::Split(split) if split.layout.hides_children() => split
+                .visible_child(focused)
+                .map(|c| c.visible_window_bounds(focused))
+                .unwrap_or_default(),
+            Self::Split(split) => split
+                .children
+                .iter()
+                .flat_map(|c| c.visible_window_bounds(focused))
+                .collect(),
+        }
+    }
+
+    /// Returns the visible window nearest to `target` in `direction`, judged by
+    /// where the windows sit on screen rather than where they sit in the tree.
+    /// When that window lives in a container that does not hold `target`,
+    /// focus enters the container at its most recently focused window that
+    /// also lies in `direction`, as in i3.
+    pub fn window_in_direction(&self, target: WindowId, direction: Direction) -> Option<WindowId> {
+        let mut windows = self.visible_window_bounds(target);
+        let from = windows.remove(&target)?;
+        let nearest = nearest_in_direction(from, windows.iter().map(|(&w, &b)| (w, b)), direction)?;
+
+        let Some(Self::Split(entered)) = self.branch_towards(target, nearest) else {
+            return Some(nearest);
+        };
+
+        let recent = entered.focus_stack.iter().rev().find(|w| {
+            windows
+                .get(w)
+                .is_some_and(|&b| lies_in_direction(from, b, direction))
+        });
+        Some(recent.copied().unwrap_or(nearest))
+    }
+
+    /// Returns the visible window furthest from `target` in `direction` that
+    /// still overlaps it on the perpendicular axis.
+    pub fn window_at_edge(&self, target: WindowId, direction: Direction) -> Option<WindowId> {
+        let mut windows = self.visible_window_bounds(target);
+        let from = windows.remove(&target)?;
+        furthest_in_direction(from, windows, direction)
+    }
+
+    /// Returns the title strips of every visible stacked split in the
+    /// container, given the currently `focused` window.
+    pub fn title_strips(&self, focused: WindowId) -> Vec<TitleStrip> {
+        let Self::Split(split) = self else {
+            return Vec::new();
+        };
+
+        if !split.layout.hides_children() {
+            return split
+                .children
+                .iter()
+                .flat_map(|c| c.title_strips(focused))
+                .collect();
+        }
+
+        let Some(visible) = split.visible_child(focused) else {
+            return Vec::new();
+        };
+
+        let mut strips = Vec::new();
+        if split.layout == Layout::Stacked {
+            let strip_bounds =
+                stacked_strip_bounds(split.bounds, split.children.len(), split.padding);
+            for (child, bounds) in split.children.iter().zip(strip_bounds) {
+                let window = if child.contains_window(focused) {
+                    focused
+                } else {
+                    child.focus_target()
+                };
+
+                strips.push(TitleStrip {
+                    bounds,
+                    window,
+                    is_active: std::ptr::eq(child, visible),
+                });
+            }
+        }
+
+        strips.extend(visible.title_strips(focused));
+        strips
+    }
+
+    /// Returns the window that takes focus when focus enters the container:
+    /// following each split's most recently focused child down to a leaf, or
+    /// its first child where none has been focused.
+    pub(super) fn focus_target(&self) -> WindowId {
+        match self {
+            Self::Leaf(leaf) => leaf.window.id,
+            Self::Split(split) => {
+                split.children[split.last_focused_child().unwrap_or(0)].focus_target()
+            }
+        }
+    }
+
+    /// Record `target` as focused in every split above it, so that focus can
+    /// later return to it when re-entering those splits.
+    pub fn record_focus(&mut self, target: WindowId) {
+        let Self::Split(split) = self else {
+            return;
+        };
+
+        split.record_focus(target);
+        if let Some(child) = split
+            .children
+            .iter_mut()
+            .find(|c| c.contains_window(target))
+        {
+            child.record_focus(target);
+        }
+    }
+
+    /// Returns the window that should take focus once `target` is closed: the
+    /// previously focused sibling of `target`, or of the innermost split above
+    /// it that has other children. A sibling that has never been focused
+    /// gives way to the one before `target`, then the one after it.
+    pub fn focus_successor(&self, target: WindowId) -> Option<WindowId> {
+        let Self::Split(split) = self else {
+            return None;
+        };
+
+        let (i, _) = split.child_containing(target).ok()?;
+        if let Some(inner) = split.children[i].focus_successor(target) {
+            return Some(inner);
+        }
+
+        let sibling = split
+            .last_focused_child_other_than(Some(i))
+            .or(i.checked_sub(1))
+            .or((i + 1 < split.children.len()).then_some(i + 1))?;
+        Some(split.children[sibling].focus_target())
+    }
+
+    /// Returns the child of the innermost split holding both `from` and `to`
+    /// that holds `to`.
+    fn branch_towards(&self, from: WindowId, to: WindowId) -> Option<&Container> {
+        let Self::Split(split) = self else {
+            return None;
+        };
+
+        let child = split.children.iter().find(|c| c.contains_window(to))?;
+        if child.contains_window(from) {
+            child.branch_towards(from, to)
+        } else {
+            Some(child)
         }
     }
 
@@ -208,9 +914,25 @@ impl Container {
 
 /// Return a list of `n` bounds spread equally across the provided region,
 /// accounting for edge, and inter-element padding.
+#[cfg(test)]
 fn spread_bounds_along_axis(original: Bounds, axis: Axis, n: usize, padding: f64) -> Vec<Bounds> {
+    weighted_bounds_along_axis(original, axis, &vec![1.0; n], &vec![0.0; n], padding)
+}
+
+/// Return a list of bounds, one per weight, spread across the provided region
+/// so that each child's share of the space along `axis` is proportional to its
+/// weight, without falling short of its minimum length in `mins`, accounting
+/// for edge, and inter-element padding.
+fn weighted_bounds_along_axis(
+    original: Bounds,
+    axis: Axis,
+    weights: &[f64],
+    mins: &[f64],
+    padding: f64,
+) -> Vec<Bounds> {
     use Axis::*;
 
+    let n = weights.len();
     assert_ne!(n, 0);
 
     let total_inner_gap = (n - 1) as f64 * (padding); // half padding between children
@@ -219,163 +941,120 @@ fn spread_bounds_along_axis(original: Bounds, axis: Axis, n: usize, padding: f64
         Vertical => original.height,
     };
     let available_space = length_to_split - 2.0 * padding - total_inner_gap;
-    let each_child_length = available_space / n as f64;
 
-    (0..n)
-        .map(|i| match axis {
-            Horizontal => Bounds {
-                x: original.x + padding + i as f64 * (each_child_length + padding),
-                y: original.y + padding,
-                width: each_child_length,
-                height: original.height - 2.0 * padding,
-            },
-            Vertical => Bounds {
-                x: original.x + padding,
-                y: original.y + padding + i as f64 * (each_child_length + padding),
-                width: original.width - 2.0 * padding,
-                height: each_child_length,
-            },
+    let mut offset = padding;
+    solve_lengths(available_space, weights, mins)
+        .into_iter()
+        .map(|child_length| {
+            let bounds = match axis {
+                Horizontal => Bounds {
+                    x: original.x + offset,
+                    y: original.y + padding,
+                    width: child_length,
+                    height: original.height - 2.0 * padding,
+                },
+                Vertical => Bounds {
+                    x: original.x + padding,
+                    y: original.y + offset,
+                    width: original.width - 2.0 * padding,
+                    height: child_length,
+                },
+            };
+            offset += child_length + padding;
+            bounds
         })
         .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use Axis::{Horizontal, Vertical};
-    use core_graphics::Bounds;
-
-    const PADDING_VALUES: &[f64] = &[0.0, 5.0, 10.0, 17.5];
-    const AXES: &[Axis] = &[Horizontal, Vertical];
-    const CHILD_NUMS: &[usize] = &[1, 2, 3, 4, 5, 6, 7, 8];
-
-    const EPSILON: f64 = 1e-10;
-
-    const fn approx(a: f64, b: f64) -> bool {
-        (a - b).abs() < EPSILON
+/// Share `available` length between children with the given `weights` and
+/// minimum lengths. Every child gets its share by weight, except that a child
+/// whose share would fall short of its minimum is held at that minimum, and the
+/// rest share what is left over by weight. Only if the minimums cannot all fit
+/// is the length shared purely by weight, leaving the caller to handle the
+/// overflow.
+fn solve_lengths(available: f64, weights: &[f64], mins: &[f64]) -> Vec<f64> {
+    let total_weight: f64 = weights.iter().sum();
+    if mins.iter().sum::<f64>() > available {
+        return weights
+            .iter()
+            .map(|weight| available * weight / total_weight)
+            .collect();
     }
 
-    pub fn dummy_bounds() -> Bounds {
-        Bounds {
-            x: 0.0,
-            y: 0.0,
-            width: 800.0,
-            height: 600.0,
-        }
-    }
+    // Holding a child at its minimum leaves less for the others, which can
+    // push further children below their own minimums, so repeat until none
+    // fall short.
+    let mut held = vec![false; weights.len()];
+    loop {
+        let free_length: f64 = available
+            - (0..mins.len())
+                .filter(|&i| held[i])
+                .map(|i| mins[i])
+                .sum::<f64>();
+        let free_weight: f64 = (0..weights.len())
+            .filter(|&i| !held[i])
+            .map(|i| weights[i])
+            .sum();
 
-    // Get the `(position, size)` of the give bounds along that given axis.
-    fn along(b: &Bounds, axis: Axis) -> (f64, f64) {
-        match axis {
-            Vertical => (b.y, b.height),
-            Horizontal => (b.x, b.width),
-        }
-    }
+        let short: Vec<usize> = (0..weights.len())
+            .filter(|&i| !held[i] && free_length * weights[i] / free_weight < mins[i])
+            .collect();
 
-    // Get the `(position, size)` of the give bounds along the axis
-    // perpendicular to `axis`.
-    fn perpendicular(b: &Bounds, axis: Axis) -> (f64, f64) {
-        let perp_axis = match axis {
-            Vertical => Horizontal,
-            Horizontal => Vertical,
-        };
-        along(b, perp_axis)
-    }
+        if short.is_empty() {
+            return (0..weights.len())
+                .map(|i| {
+                    if held[i] {
+                        mins[i]
+                    } else {
+                        free_length * weights[i] / free_weight
+                    }
+                })
+                .collect();
+        }
 
-    fn transpose(b: Bounds) -> Bounds {
-        Bounds {
-            x: b.y,
-            y: b.x,
-            width: b.height,
-            height: b.width,
+        for i in short {
+            held[i] = true;
         }
     }
+}
 
-    #[test]
-    fn spread_bounds() {
-        let original = dummy_bounds();
-
-        for &padding in PADDING_VALUES {
-            for &axis in AXES {
-                for &n in CHILD_NUMS {
-                    let out = spread_bounds_along_axis(original, axis, n, padding);
-                    assert_eq!(out.len(), n);
-                    assert!(approx(out[0].x, original.x + padding));
-                    assert!(approx(out[0].y, original.y + padding));
-
-                    let total_inner_gap = (n - 1) as f64 * padding;
-
-                    let available_space = match axis {
-                        Vertical => original.height - 2.0 * padding - total_inner_gap,
-                        Horizontal => original.width - 2.0 * padding - total_inner_gap,
-                    };
-                    let child_share = available_space / n as f64;
-                    let expected_unchanged_dimension = match axis {
-                        Vertical => original.width - 2.0 * padding,
-                        Horizontal => original.height - 2.0 * padding,
-                    };
-
-                    let last = &out[n - 1];
-                    match axis {
-                        Vertical => assert!(approx(
-                            last.y + last.height,
-                            original.y + original.height - padding
-                        )),
-                        Horizontal => assert!(approx(
-                            last.x + last.width,
-                            original.x + original.width - padding
-                        )),
-                    }
+/// Return a list of `n` identical bounds that each fill the provided region,
+/// accounting for edge padding. Used by layouts whose children overlap.
+fn overlap_bounds(original: Bounds, n: usize, padding: f64) -> Vec<Bounds> {
+    assert_ne!(n, 0);
 
-                    // The original bounds (x/y) positions dependent on axis
-                    let (orig_pos, _) = along(&original, axis);
-                    let (original_perp_pos, _) = perpendicular(&original, axis);
-
-                    for (i, b) in out.iter().enumerate() {
-                        let (pos, size) = along(b, axis);
-                        let (perp_pos, perp_size) = perpendicular(b, axis);
-
-                        // Each child should be the same, correct size
-                        assert!(approx(size, child_share));
-                        // The dimension perpendicular to the axis we are
-                        // spreading along should just be what it was originally
-                        // (accounting for padding)
-                        assert!(approx(perp_size, expected_unchanged_dimension));
-                        // Accounting for padding, all children should have the
-                        // same (x/y) value perpendicular to the spreading axis
-                        assert!(approx(perp_pos, original_perp_pos + padding));
-                        // All children should have equally spaced including
-                        // padding in between them
-                        assert!(approx(
-                            pos,
-                            orig_pos + padding + i as f64 * (child_share + padding)
-                        ));
-                    }
+    vec![original.with_pad(padding); n]
+}
 
-                    let covered = match axis {
-                        Vertical => out.iter().map(|b| b.height).sum(),
-                        Horizontal => out.iter().map(|b| b.width).sum(),
-                    };
-                    assert!(approx(covered, available_space));
-                }
-            }
-        }
-    }
+/// Return the bounds of the `n` title strips at the top of a stacked region,
+/// one below the other, accounting for edge padding.
+fn stacked_strip_bounds(original: Bounds, n: usize, padding: f64) -> Vec<Bounds> {
+    let inner = original.with_pad(padding);
 
-    #[test]
-    fn spread_bounds_along_axis_symmetry() {
-        let original = dummy_bounds();
-        let transposed = transpose(original);
+    (0..n)
+        .map(|i| Bounds {
+            y: inner.y + i as f64 * TITLE_STRIP_HEIGHT,
+            height: TITLE_STRIP_HEIGHT,
+            ..inner
+        })
+        .collect()
+}
 
-        let padding = 12.0;
-        let n = 4;
+/// Return a list of `n` identical bounds that each fill the provided region
+/// below the `n` title strips of a stacked layout, accounting for edge
+/// padding.
+fn stacked_child_bounds(original: Bounds, n: usize, padding: f64) -> Vec<Bounds> {
+    assert_ne!(n, 0);
 
-        let h = spread_bounds_along_axis(original, Horizontal, n, padding);
-        let v = spread_bounds_along_axis(transposed, Vertical, n, padding);
+    let inner = original.with_pad(padding);
+    let strips_height = n as f64 * TITLE_STRIP_HEIGHT;
 
-        for (bh, bv) in h.iter().zip(v.iter()) {
-            assert!(approx(bh.width, bv.height));
-            assert!(approx(bh.height, bv.width));
-        }
-    }
+    vec![
+        Bounds {
+            y: inner.y + strips_height,
+            height: inner.height - strips_height,
+            ..inner
+        };
+        n
+    ]
 }
@@ -0,0 +1,85 @@
+use crate::container::Axis;
+use crate::ctl::{Dimension, ResizeAmount, ResizeArg};
+use std::fmt::Display;
+use std::ops::Neg;
+
+/// A length given either in pixels, or in percentage points of the length of
+/// the split it is measured within.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Length {
+    Px(f64),
+    Ppt(f64),
+}
+
+impl Length {
+    /// Return the length in pixels, resolving percentage points against
+    /// `whole`.
+    pub fn resolve(self, whole: f64) -> f64 {
+        match self {
+            Self::Px(px) => px,
+            Self::Ppt(ppt) => whole * ppt / 100.0,
+        }
+    }
+}
+
+impl Neg for Length {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self {
+            Self::Px(px) => Self::Px(-px),
+            Self::Ppt(ppt) => Self::Ppt(-ppt),
+        }
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Px(px) => write!(f, "{px} px"),
+            Self::Ppt(ppt) => write!(f, "{ppt} ppt"),
+        }
+    }
+}
+
+impl From<ResizeAmount> for Option<Length> {
+    /// Tiled containers are resized by percentage points in preference to
+    /// pixels, as in i3's `<n> px or <m> ppt`.
+    fn from(amount: ResizeAmount) -> Self {
+        amount.ppt.map(Length::Ppt).or(amount.px.map(Length::Px))
+    }
+}
+
+/// A requested change to the size of a selected container. A missing length
+/// falls back to the configured resize step.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Resize {
+    Grow(Axis, Option<Length>),
+    Shrink(Axis, Option<Length>),
+    Set {
+        width: Option<Length>,
+        height: Option<Length>,
+    },
+}
+
+impl From<Dimension> for Axis {
+    fn from(dimension: Dimension) -> Self {
+        match dimension {
+            Dimension::Width => Self::Horizontal,
+            Dimension::Height => Self::Vertical,
+        }
+    }
+}
+
+impl From<ResizeArg> for Resize {
+    fn from(arg: ResizeArg) -> Self {
+        match arg {
+            ResizeArg::Grow(dimension, amount) => Self::Grow(dimension.into(), amount.into()),
+            ResizeArg::Shrink(dimension, amount) => Self::Shrink(dimension.into(), amount.into()),
+            ResizeArg::Set { width, height } => Self::Set {
+                width: width.and_then(Into::into),
+                height: height.and_then(Into::into),
+            },
+        }
+    }
+}
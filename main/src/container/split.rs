@@ -1,19 +1,28 @@
-use crate::container::RemoveResult;
+use crate::container::{MoveResult, RemoveResult};
 use crate::{
-    container::{Axis, Container, Window, leaf::Leaf, spread_bounds_along_axis},
+    container::{
+        Axis, Container, Layout, LayoutChange, TITLE_STRIP_HEIGHT, Window, leaf::Leaf,
+        overlap_bounds, stacked_child_bounds, weighted_bounds_along_axis,
+    },
     error::{Error, Result},
-    window_manager,
 };
 use core_graphics::{Bounds, Direction, WindowId};
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub(crate) struct Split {
     pub bounds: Bounds,
     pub axis: Axis,
+    pub layout: Layout,
     pub padding: f64,
     pub children: Vec<Container>,
+    /// The relative share of the split's space along its axis given to each
+    /// child, index for index. Only the ratios between weights matter.
+    pub weights: Vec<f64>,
+    /// The windows focused within the split, most recent last. The split's
+    /// last focused child is the one holding the most recent of these.
+    pub focus_stack: Vec<WindowId>,
 }
 
 impl Split {
@@ -21,57 +30,196 @@ impl Split {
         Self {
             bounds,
             axis,
+            layout: Layout::default(),
             padding,
+            weights: vec![1.0; children.len()],
             children,
+            focus_stack: Vec::new(),
         }
     }
 
-    pub fn min_width(&self) -> f64 {
+    /// Return the bounds the split's children should have, according to its
+    /// layout, their weights, and their minimum sizes.
+    fn child_bounds(&self, padding: f64) -> Vec<Bounds> {
+        let n = self.children.len();
+        match self.layout {
+            Layout::Tiled => weighted_bounds_along_axis(
+                self.bounds,
+                self.axis,
+                &self.weights,
+                &self.child_mins(),
+                padding,
+            ),
+            Layout::Tabbed => overlap_bounds(self.bounds, n, padding),
+            Layout::Stacked => stacked_child_bounds(self.bounds, n, padding),
+        }
+    }
+
+    /// Resize every child to the bounds derived from the split's weights.
+    pub(super) fn relayout_children(&mut self, padding: f64) -> Result<()> {
+        if self.children.is_empty() {
+            return Ok(());
+        }
+
+        let new_bounds = self.child_bounds(padding);
+        for (child, b) in self.children.iter_mut().zip(new_bounds) {
+            child.resize(b)?;
+        }
+
+        Ok(())
+    }
+
+    /// Return each child's minimum length along the split's axis.
+    fn child_mins(&self) -> Vec<f64> {
         self.children
             .iter()
-            .map(|c| c.min_width())
-            .fold(0.0, f64::max)
+            .map(|c| c.min_length(self.axis))
+            .collect()
     }
 
-    pub fn min_height(&self) -> f64 {
+    /// Recalculate the split's weights from the current lengths of its
+    /// children along its axis, so that a manual resize is remembered.
+    fn sync_weights_to_bounds(&mut self) {
+        self.weights = self
+            .children
+            .iter()
+            .map(|c| self.axis.length_of(c.bounds()))
+            .collect();
+    }
+
+    /// Return the child shown by a tabbed or stacked split: the one containing
+    /// `focused`, or the last focused child if `focused` lives elsewhere.
+    pub fn visible_child(&self, focused: WindowId) -> Option<&Container> {
         self.children
             .iter()
-            .map(|c| c.min_height())
-            .fold(0.0, f64::max)
+            .find(|c| c.contains_window(focused))
+            .or_else(|| self.children.get(self.last_focused_child().unwrap_or(0)))
+    }
+
+    /// Move `window` to the top of the split's focus stack.
+    pub(super) fn record_focus(&mut self, window: WindowId) {
+        self.focus_stack.retain(|w| *w != window);
+        self.focus_stack.push(window);
+    }
+
+    /// Return the index of the child that most recently held focus, if any
+    /// child has held focus since it joined the split.
+    pub fn last_focused_child(&self) -> Option<usize> {
+        self.last_focused_child_other_than(None)
+    }
+
+    /// Return the index of the child, other than the one at `skip`, that most
+    /// recently held focus.
+    pub(super) fn last_focused_child_other_than(&self, skip: Option<usize>) -> Option<usize> {
+        self.focus_stack.iter().rev().find_map(|&window| {
+            self.children
+                .iter()
+                .position(|c| c.contains_window(window))
+                .filter(|&i| Some(i) != skip)
+        })
+    }
+
+    /// Change the split's layout, and re-lay out its children to match.
+    pub fn set_layout(&mut self, layout: Layout, padding: f64) -> Result<()> {
+        self.layout = layout;
+        self.relayout_children(padding)
+    }
+
+    /// Apply a requested layout change, flipping the split's axis when a tiled
+    /// split is toggled. Children keep their weights, so each keeps the same
+    /// share of the split along the new axis.
+    pub fn change_layout(&mut self, change: LayoutChange, padding: f64) -> Result<Layout> {
+        if change == LayoutChange::ToggleSplit && self.layout == Layout::Tiled {
+            self.axis = self.axis.perpendicular();
+        }
+
+        let layout = change.apply(self.layout);
+        self.set_layout(layout, padding)?;
+        Ok(layout)
+    }
+
+    /// Reset the split, and every split below it, to give each child an equal
+    /// share of the space, then lay the split out again.
+    pub fn balance(&mut self, padding: f64) -> Result<()> {
+        self.reset_weights();
+        self.relayout_children(padding)
+    }
+
+    fn reset_weights(&mut self) {
+        self.weights = vec![1.0; self.children.len()];
+        for child in &mut self.children {
+            if let Container::Split(split) = child {
+                split.reset_weights();
+            }
+        }
+    }
+
+    pub fn min_width(&self) -> f64 {
+        self.min_length(Axis::Horizontal)
+    }
+
+    pub fn min_height(&self) -> f64 {
+        self.min_length(Axis::Vertical)
+    }
+
+    /// Return the shortest the split can be along `axis` with every child at
+    /// or above its minimum length, including padding. Children laid side by
+    /// side along `axis` need the sum of their minimums, while children that
+    /// share the split's length need only the largest.
+    fn min_length(&self, axis: Axis) -> f64 {
+        if self.children.is_empty() {
+            return 0.0;
+        }
+
+        let n = self.children.len() as f64;
+        let mins = self.children.iter().map(|c| c.min_length(axis));
+
+        match self.layout {
+            Layout::Tiled if self.axis == axis => mins.sum::<f64>() + (n + 1.0) * self.padding,
+            Layout::Stacked if axis == Axis::Vertical => {
+                mins.fold(0.0, f64::max) + n * TITLE_STRIP_HEIGHT + 2.0 * self.padding
+            }
+            _ => mins.fold(0.0, f64::max) + 2.0 * self.padding,
+        }
     }
 
     // To add a window to a split container:
-    //  1. Create the new window and add it to the split's children.
-    //  2. Spread the containers bounds across the now N children.
-    //  3. Resize all children using those new bounds.
+    //  1. Add the new window to the split's children, giving it the average
+    //     weight of the existing children, so it takes an equal share of the
+    //     space, and the existing children give it up in proportion to their
+    //     own weights.
+    //  2. If the children's minimum sizes can no longer fit in the split, take
+    //     the new window back out and error.
+    //  3. Resize all children using the bounds derived from the new weights.
     pub fn add_window(&mut self, window: Window, padding: f64) -> Result<()> {
-        let num_new_children = self.children.len() + 1;
-        let new_bounds =
-            spread_bounds_along_axis(self.bounds, self.axis, num_new_children, padding);
+        self.push_window(window, padding);
 
-        if self
-            .children
-            .iter()
-            .zip(&new_bounds)
-            .any(|(c, b)| b.width < c.min_width() || b.height < c.min_height())
-        {
+        if !self.fits() {
+            self.children.pop();
+            self.weights.pop();
             return Err(Error::CannotFitWindow);
         }
 
-        // Also check new window
-        let last_bounds = new_bounds.last().unwrap();
-        if last_bounds.width < window.min_width || last_bounds.height < window.min_height {
-            return Err(Error::CannotFitWindow);
-        }
+        self.relayout_children(padding)
+    }
 
-        let new_child = Container::Leaf(Leaf::new(*new_bounds.last().unwrap(), padding, window));
-        self.children.push(new_child);
+    /// Add `window` as the split's last child, with the average weight of the
+    /// existing children, without laying the split out again.
+    pub(super) fn push_window(&mut self, window: Window, padding: f64) {
+        let new_weight = if self.weights.is_empty() {
+            1.0
+        } else {
+            self.weights.iter().sum::<f64>() / self.weights.len() as f64
+        };
 
-        for (child, new_bounds) in self.children.iter_mut().zip(new_bounds) {
-            child.resize(new_bounds)?;
-        }
+        self.children
+            .push(Container::Leaf(Leaf::new(self.bounds, padding, window)));
+        self.weights.push(new_weight);
+    }
 
-        Ok(())
+    /// Whether the split's bounds can hold every child at its minimum size.
+    pub(super) fn fits(&self) -> bool {
+        self.min_width() <= self.bounds.width && self.min_height() <= self.bounds.height
     }
 
     pub fn window_ids(&self) -> HashSet<WindowId> {
@@ -88,86 +236,123 @@ impl Split {
             .collect()
     }
 
-    pub fn resize(&mut self, new_bounds: Bounds) -> Result<()> {
-        let old_bounds = self.bounds;
-        self.bounds = new_bounds;
-
-        for child in self.children.iter_mut() {
-            let cb = child.bounds();
-            let new_cb = match self.axis {
-                Axis::Horizontal => {
-                    let x_ratio = (cb.x - old_bounds.x) / old_bounds.width;
-                    let w_ratio = cb.width / old_bounds.width;
-                    Bounds {
-                        x: new_bounds.x + x_ratio * new_bounds.width,
-                        y: self.bounds.y + self.padding,
-                        width: w_ratio * new_bounds.width,
-                        height: self.bounds.height - 2.0 * self.padding,
-                    }
-                }
-                Axis::Vertical => {
-                    let y_ratio = (cb.y - old_bounds.y) / old_bounds.height;
-                    let h_ratio = cb.height / old_bounds.height;
-                    Bounds {
-                        x: self.bounds.x + self.padding,
-                        y: new_bounds.y + y_ratio * new_bounds.height,
-                        width: self.bounds.width - 2.0 * self.padding,
-                        height: h_ratio * new_bounds.height,
-                    }
-                }
+    // To flatten the split's subtree, bottom up:
+    //  - Flatten every child split first.
+    //  - Drop any child split left empty.
+    //  - Replace any child split with a single child by that child, which
+    //    takes over the weight of the split it replaces.
+    //  - Splice the children of a tiled child split running along the same
+    //    axis as this tiled split into this split, sharing the child's weight
+    //    out between them in proportion to their own weights, so every window
+    //    keeps its share of the space.
+    //  - If a single child split remains, take on its axis, layout, children
+    //    and weights, as this split cannot be replaced in its own parent here.
+    pub(super) fn flatten(&mut self) {
+        let children = std::mem::take(&mut self.children);
+        let weights = std::mem::take(&mut self.weights);
+
+        for (child, weight) in children.into_iter().zip(weights) {
+            let Container::Split(mut split) = child else {
+                self.children.push(child);
+                self.weights.push(weight);
+                continue;
             };
 
-            child.resize(new_cb)?;
+            split.flatten();
+
+            if split.children.is_empty() {
+                continue;
+            }
+
+            if split.children.len() == 1 {
+                // Safety: the split was just checked to hold a single child.
+                self.children.push(split.children.pop().unwrap());
+                self.weights.push(weight);
+            } else if self.layout == Layout::Tiled
+                && split.layout == Layout::Tiled
+                && split.axis == self.axis
+            {
+                let total: f64 = split.weights.iter().sum();
+                self.weights
+                    .extend(split.weights.iter().map(|w| weight * w / total));
+                self.children.append(&mut split.children);
+            } else {
+                self.children.push(Container::Split(split));
+                self.weights.push(weight);
+            }
         }
 
-        Ok(())
+        if self.children.len() == 1
+            && let Container::Split(only) = &mut self.children[0]
+        {
+            self.axis = only.axis;
+            self.layout = only.layout;
+            self.weights = std::mem::take(&mut only.weights);
+            self.children = std::mem::take(&mut only.children);
+        }
+    }
+
+    /// Whether every split below this one is canonical: it holds at least two
+    /// children, one weight per child, and is not a tiled split directly
+    /// inside a tiled split along the same axis.
+    pub(super) fn children_are_normalised(&self) -> bool {
+        self.weights.len() == self.children.len()
+            && self.children.iter().all(|child| match child {
+                Container::Leaf(_) => true,
+                Container::Split(split) => {
+                    split.children.len() >= 2
+                        && !(self.layout == Layout::Tiled
+                            && split.layout == Layout::Tiled
+                            && split.axis == self.axis)
+                        && split.children_are_normalised()
+                }
+            })
+    }
+
+    pub fn resize(&mut self, new_bounds: Bounds) -> Result<()> {
+        self.bounds = new_bounds;
+        self.relayout_children(self.padding)
     }
 
     pub fn remove_window(&mut self, id: WindowId, padding: f64) -> Result<RemoveResult> {
+        self.focus_stack.retain(|w| *w != id);
+
         if let Some(pos) = self
             .children
             .iter()
             .position(|c| matches!(c, Container::Leaf(leaf) if leaf.window.id == id))
         {
             self.children.remove(pos);
+            self.weights.remove(pos);
 
             if self.children.is_empty() {
                 return Ok(RemoveResult::BecomeEmpty);
             }
 
-            let new_bounds =
-                spread_bounds_along_axis(self.bounds, self.axis, self.children.len(), padding);
-            for (child, b) in self.children.iter_mut().zip(new_bounds) {
-                child.resize(b)?;
-            }
-
+            self.relayout_children(padding)?;
             return Ok(RemoveResult::Removed);
         }
 
         // Recursive case
         for i in 0..self.children.len() {
-            match self.children[i].remove_window(id, padding)? {
+            let Container::Split(child) = &mut self.children[i] else {
+                continue;
+            };
+
+            match child.remove_window(id, padding)? {
                 RemoveResult::NotFound => continue,
                 RemoveResult::Removed => {
                     return Ok(RemoveResult::Removed);
                 }
                 RemoveResult::BecomeEmpty => {
                     self.children.remove(i);
+                    self.weights.remove(i);
 
                     if self.children.is_empty() {
                         return Ok(RemoveResult::BecomeEmpty);
                     }
 
-                    let new_bounds = spread_bounds_along_axis(
-                        self.bounds,
-                        self.axis,
-                        self.children.len(),
-                        padding,
-                    );
-                    for (child, b) in self.children.iter_mut().zip(new_bounds) {
-                        child.resize(b)?;
-                    }
-
+                    self.relayout_children(padding)?;
                     return Ok(RemoveResult::Removed);
                 }
             }
@@ -176,6 +361,174 @@ impl Split {
         Ok(RemoveResult::NotFound)
     }
 
+    /// The axis along which the split's children are ordered. Tabs read left
+    /// to right, and stacks top to bottom, whatever the split's own axis is.
+    pub fn orientation(&self) -> Axis {
+        match self.layout {
+            Layout::Tiled => self.axis,
+            Layout::Tabbed => Axis::Horizontal,
+            Layout::Stacked => Axis::Vertical,
+        }
+    }
+
+    // To move the selected container -- the one `depth` levels above the leaf
+    // managing `target` -- within a split, in the style of i3's `move`:
+    //  - If the selection is a direct child, the split's orientation matches
+    //    the direction, and there is a sibling on that side:
+    //     - If the sibling is a leaf, swap the two.
+    //     - If the sibling is a split, move the selection into it; at its near
+    //       end if it has the same orientation, otherwise at its end.
+    //  - If the selection lives in a child split, let that split move it first.
+    //    If the selection is at the edge of that child and this split's
+    //    orientation matches the direction, take it out of the child and place
+    //    it next to the child on the requested side.
+    //  - Otherwise the selection is at the edge of this split, and it is up to
+    //    an ancestor to move it.
+    pub fn move_container(
+        &mut self,
+        target: WindowId,
+        depth: usize,
+        direction: Direction,
+        padding: f64,
+    ) -> Result<MoveResult> {
+        let (i, child_depth) = self.child_containing(target)?;
+
+        let matches_orientation = self.orientation() == Axis::from(direction);
+        let forwards = matches!(direction, Direction::Right | Direction::Down);
+
+        if child_depth > depth {
+            let Container::Split(child) = &mut self.children[i] else {
+                unreachable!("only splits have windows below them");
+            };
+
+            if child.move_container(target, depth, direction, padding)? == MoveResult::Moved {
+                return Ok(MoveResult::Moved);
+            }
+
+            if !matches_orientation {
+                return Ok(MoveResult::AtEdge);
+            }
+
+            let selected = child.take_container(target, depth, padding)?;
+            let mut index = if forwards { i + 1 } else { i };
+            if child.children.is_empty() {
+                self.children.remove(i);
+                self.weights.remove(i);
+                if forwards {
+                    index -= 1;
+                }
+            }
+
+            self.insert_child(index, selected, padding)?;
+            return Ok(MoveResult::Moved);
+        }
+
+        let j = if forwards {
+            i + 1
+        } else {
+            match i.checked_sub(1) {
+                Some(j) => j,
+                None => return Ok(MoveResult::AtEdge),
+            }
+        };
+
+        if !matches_orientation || j >= self.children.len() {
+            return Ok(MoveResult::AtEdge);
+        }
+
+        if matches!(self.children[j], Container::Leaf(_)) {
+            self.children.swap(i, j);
+            self.weights.swap(i, j);
+            self.relayout_children(padding)?;
+            return Ok(MoveResult::Moved);
+        }
+
+        let selected = self.children.remove(i);
+        self.weights.remove(i);
+
+        let j = if forwards { j - 1 } else { j };
+        let Container::Split(sibling) = &mut self.children[j] else {
+            unreachable!("non-leaf children are splits");
+        };
+
+        let index = if forwards && sibling.orientation() == Axis::from(direction) {
+            0
+        } else {
+            sibling.children.len()
+        };
+        sibling.insert_child(index, selected, padding)?;
+
+        self.relayout_children(padding)?;
+        Ok(MoveResult::Moved)
+    }
+
+    /// Return the index of the child containing `target`, and how many splits
+    /// lie between that child and the leaf managing `target`.
+    pub(super) fn child_containing(&self, target: WindowId) -> Result<(usize, usize)> {
+        self.children
+            .iter()
+            .enumerate()
+            .find_map(|(i, c)| c.depth_of_window(target).map(|depth| (i, depth)))
+            .ok_or(Error::WindowNotFound)
+    }
+
+    /// Remove the selected container -- the one `depth` levels above the leaf
+    /// managing `target` -- from anywhere below the split, removing any child
+    /// splits left empty, and return it.
+    pub fn take_container(
+        &mut self,
+        target: WindowId,
+        depth: usize,
+        padding: f64,
+    ) -> Result<Container> {
+        let (i, child_depth) = self.child_containing(target)?;
+
+        let taken = match child_depth.cmp(&depth) {
+            std::cmp::Ordering::Less => return Err(Error::CannotFindSelection),
+            std::cmp::Ordering::Equal => {
+                self.weights.remove(i);
+                self.children.remove(i)
+            }
+            std::cmp::Ordering::Greater => {
+                let Container::Split(child) = &mut self.children[i] else {
+                    unreachable!("only splits have windows below them");
+                };
+
+                let taken = child.take_container(target, depth, padding)?;
+                if child.children.is_empty() {
+                    self.children.remove(i);
+                    self.weights.remove(i);
+                }
+                taken
+            }
+        };
+
+        self.relayout_children(padding)?;
+        Ok(taken)
+    }
+
+    /// Insert `child` at `index`, giving it the average weight of the existing
+    /// children, and re-lay out the split.
+    pub fn insert_child(&mut self, index: usize, child: Container, padding: f64) -> Result<()> {
+        let weight = if self.weights.is_empty() {
+            1.0
+        } else {
+            self.weights.iter().sum::<f64>() / self.weights.len() as f64
+        };
+
+        self.children.insert(index, child);
+        self.weights.insert(index, weight);
+        self.relayout_children(padding)
+    }
+
+    /// Wrap the split in a new split along `axis`, as its only child.
+    pub fn wrap(self, axis: Axis) -> Split {
+        let outer_bounds = self.bounds.with_pad(-self.padding);
+        let padding = self.padding;
+
+        Split::new(outer_bounds, axis, padding, vec![Container::Split(self)])
+    }
+
     pub fn split(&mut self, axis: Axis) -> Result<()> {
         if self.children.len() < 2 {
             self.axis = axis;
@@ -185,46 +538,90 @@ impl Split {
         }
     }
 
-    pub fn resize_window(
+    /// Return the split directly holding the selected container -- the one
+    /// `depth` levels above the leaf managing `target` -- and the selection's
+    /// index within it.
+    pub(super) fn selection_parent_mut(
         &mut self,
         target: WindowId,
-        direction: Direction,
+        depth: usize,
+    ) -> Result<(&mut Self, usize)> {
+        let (i, child_depth) = self.child_containing(target)?;
+
+        if child_depth > depth {
+            let Container::Split(child) = &mut self.children[i] else {
+                return Err(Error::CannotFindSelection);
+            };
+            return child.selection_parent_mut(target, depth);
+        }
+
+        Ok((self, i))
+    }
+
+    /// Whether the split has a divider along `axis` that can be moved: tabs
+    /// and stacks all share the split's bounds, and a lone child has no
+    /// neighbour.
+    pub(super) fn can_resize_along(&self, axis: Axis) -> bool {
+        !self.layout.hides_children() && self.children.len() >= 2 && self.axis == axis
+    }
+
+    /// Return the index of the sibling of child `i` on its `direction` side,
+    /// if the split runs along `direction` and there is one.
+    pub(super) fn neighbour_towards(&self, i: usize, direction: Direction) -> Option<usize> {
+        if !self.can_resize_along(Axis::from(direction)) {
+            return None;
+        }
+
+        match direction {
+            Direction::Left | Direction::Up => i.checked_sub(1),
+            Direction::Right | Direction::Down => Some(i + 1).filter(|&j| j < self.children.len()),
+        }
+    }
+
+    /// Grow child `i` by `delta` at the expense of its next sibling, or of its
+    /// previous sibling when it is the last child, as i3 does.
+    pub(super) fn grow_child(
+        &mut self,
+        i: usize,
+        delta: f64,
+        min: f64,
         padding: f64,
     ) -> Result<()> {
-        let i = self
-            .children
-            .iter()
-            .position(|c| c.contains_window(target))
-            .ok_or(Error::WindowNotFound)?;
-
-        if !self.axis.can_resize_in_direction(direction) {
-            return self.children[i].resize_window(target, direction, padding);
+        if i + 1 < self.children.len() {
+            self.move_divider(i, delta, min, padding)
+        } else {
+            self.move_divider(i - 1, -delta, min, padding)
         }
+    }
 
-        let n = if i == 0 { 1 } else { i - 1 };
-        let (left, right) = self.children.split_at_mut(i.max(n));
-        let (a, b) = (&mut left[i.min(n)], &mut right[0]);
+    /// Move the divider between children `a` and `a + 1` by `delta` along the
+    /// split's axis, then remember the new sizes in the split's weights.
+    pub(super) fn move_divider(
+        &mut self,
+        a: usize,
+        delta: f64,
+        min: f64,
+        padding: f64,
+    ) -> Result<()> {
+        let (left, right) = self.children.split_at_mut(a + 1);
+        Self::resize_at_split(self.axis, &mut left[a], &mut right[0], delta, min, padding)?;
+        self.sync_weights_to_bounds();
 
-        Self::resize_at_split(self.axis, a, b, direction, padding)
+        Ok(())
     }
 
     fn resize_at_split(
         axis: Axis,
         a: &mut Container,
         b: &mut Container,
-        direction: Direction,
+        delta: f64,
+        min: f64,
         padding: f64,
     ) -> Result<()> {
         use Axis::*;
-        use Direction::*;
-        use window_manager::{MIN_WINDOW_SIZE, RESIZE_AMOUNT};
 
         let (first_bounds, second_bounds) = (a.bounds(), b.bounds());
-
-        let delta = match direction {
-            Left | Up => -RESIZE_AMOUNT,
-            Right | Down => RESIZE_AMOUNT,
-        };
+        let (first_min, second_min) = (a.min_length(axis).max(min), b.min_length(axis).max(min));
 
         let midpoint = match axis {
             Vertical => first_bounds.y + first_bounds.height + padding / 2.0,
@@ -233,13 +630,12 @@ impl Split {
 
         let new_midpoint = match axis {
             Vertical => (midpoint + delta)
-                .max(first_bounds.y + MIN_WINDOW_SIZE)
-                .min(first_bounds.y + first_bounds.height + second_bounds.height - MIN_WINDOW_SIZE),
+                .max(first_bounds.y + first_min)
+                .min(first_bounds.y + first_bounds.height + second_bounds.height - second_min),
             Horizontal => (midpoint + delta)
-                .max(first_bounds.x + MIN_WINDOW_SIZE)
-                .min(first_bounds.x + first_bounds.width + second_bounds.width - MIN_WINDOW_SIZE),
+                .max(first_bounds.x + first_min)
+                .min(first_bounds.x + first_bounds.width + second_bounds.width - second_min),
         };
-
         // The new midpoint is already less than a pixel away from the
         // current midpoint, so it cannot move.
         if (new_midpoint - midpoint).abs() < 1.0 {
@@ -1,763 +1,2149 @@
-#[cfg(test)]
-mod tests {
-    use crate::container::axis::Axis;
-    use crate::container::{Container, Window, spread_bounds_along_axis};
-    use core_graphics::{Bounds, WindowId};
-    use std::collections::HashSet;
+use super::*;
+use Axis::{Horizontal, Vertical};
+use core_graphics::Bounds;
+use std::collections::HashSet;
 
-    const EPSILON: f64 = 1e-10;
+const PADDING_VALUES: &[f64] = &[0.0, 5.0, 10.0, 17.5];
+const AXES: &[Axis] = &[Horizontal, Vertical];
+const CHILD_NUMS: &[usize] = &[1, 2, 3, 4, 5, 6, 7, 8];
 
-    const fn approx(a: f64, b: f64) -> bool {
-        (a - b).abs() < EPSILON
-    }
-
-    fn dummy_bounds() -> Bounds {
-        Bounds {
-            x: 0.0,
-            y: 0.0,
-            width: 800.0,
-            height: 600.0,
-        }
-    }
+const EPSILON: f64 = 1e-10;
 
-    fn dummy_window(id: WindowId) -> Window {
-        Window {
-            id,
-            min_width: 100.0,
-            min_height: 100.0,
-        }
-    }
-
-    fn dummy_empty() -> Container {
-        Container::Empty {
-            bounds: dummy_bounds(),
-        }
-    }
+const fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() < EPSILON
+}
 
-    fn dummy_leaf(window_id: WindowId) -> Container {
-        Container::Leaf {
-            bounds: dummy_bounds(),
-            padding: 0.0,
-            window: dummy_window(window_id),
-        }
+pub fn dummy_bounds() -> Bounds {
+    Bounds {
+        x: 0.0,
+        y: 0.0,
+        width: 800.0,
+        height: 600.0,
     }
+}
 
-    // This is not a valid `Container` since the child bounds are wrong. It only
-    // servers to be used in tests that are not checking correctness of bounds.
-    fn dummy_split(axis: Axis, window_ids: &[WindowId]) -> Container {
-        Container::Split {
-            bounds: dummy_bounds(),
-            padding: 0.0,
-            axis,
-            children: window_ids.iter().map(|id| dummy_leaf(*id)).collect(),
-        }
+// Get the `(position, size)` of the give bounds along that given axis.
+fn along(b: &Bounds, axis: Axis) -> (f64, f64) {
+    match axis {
+        Vertical => (b.y, b.height),
+        Horizontal => (b.x, b.width),
     }
+}
 
-    #[test]
-    fn get_bounds() {
-        let empty = dummy_empty();
-        let leaf = dummy_leaf(WindowId::from(1u32));
-        let split = dummy_split(Axis::default(), &[WindowId::from(1u32)]);
+// Get the `(position, size)` of the give bounds along the axis
+// perpendicular to `axis`.
+fn perpendicular(b: &Bounds, axis: Axis) -> (f64, f64) {
+    let perp_axis = match axis {
+        Vertical => Horizontal,
+        Horizontal => Vertical,
+    };
+    along(b, perp_axis)
+}
 
-        assert_eq!(empty.bounds(), dummy_bounds());
-        assert_eq!(leaf.bounds(), dummy_bounds());
-        assert_eq!(split.bounds(), dummy_bounds());
+fn transpose(b: Bounds) -> Bounds {
+    Bounds {
+        x: b.y,
+        y: b.x,
+        width: b.height,
+        height: b.width,
     }
+}
 
-    #[test]
-    fn contains_window() {
-        assert!(!dummy_empty().contains_window(WindowId::from(1u32)));
+#[test]
+fn spread_bounds() {
+    let original = dummy_bounds();
 
-        let target = WindowId::from(1u32);
-        let leaf_with = dummy_leaf(target);
-        let leaf_without = dummy_leaf(WindowId::from(2u32));
-        let split_with = dummy_split(Axis::Horizontal, &[target]);
-        let split_without = dummy_split(Axis::Horizontal, &[WindowId::from(2u32)]);
+    for &padding in PADDING_VALUES {
+        for &axis in AXES {
+            for &n in CHILD_NUMS {
+                let out = spread_bounds_along_axis(original, axis, n, padding);
+                assert_eq!(out.len(), n);
+                assert!(approx(out[0].x, original.x + padding));
+                assert!(approx(out[0].y, original.y + padding));
 
-        assert!(leaf_with.contains_window(target));
-        assert!(!leaf_without.contains_window(target));
-        assert!(split_with.contains_window(target));
-        assert!(!split_without.contains_window(target));
-    }
+                let total_inner_gap = (n - 1) as f64 * padding;
 
-    #[test]
-    fn getting_window_bounds_from_non_leaf_is_none() {
-        let empty = dummy_empty();
-        assert!(empty.window_bounds().is_none());
+                let available_space = match axis {
+                    Vertical => original.height - 2.0 * padding - total_inner_gap,
+                    Horizontal => original.width - 2.0 * padding - total_inner_gap,
+                };
+                let child_share = available_space / n as f64;
+                let expected_unchanged_dimension = match axis {
+                    Vertical => original.width - 2.0 * padding,
+                    Horizontal => original.height - 2.0 * padding,
+                };
 
-        let split = dummy_split(Axis::default(), &[]);
-        assert!(split.window_bounds().is_none());
-    }
+                let last = &out[n - 1];
+                match axis {
+                    Vertical => assert!(approx(
+                        last.y + last.height,
+                        original.y + original.height - padding
+                    )),
+                    Horizontal => assert!(approx(
+                        last.x + last.width,
+                        original.x + original.width - padding
+                    )),
+                }
 
-    #[test]
-    fn leaf_window_bounds_includes_correct_padding() {
-        let container_bounds = dummy_bounds();
-        const PADDING: f64 = 10.0;
-        let exp_window_bounds = Bounds {
-            height: container_bounds.height - 20.0,
-            width: container_bounds.width - 20.0,
-            x: container_bounds.x + PADDING,
-            y: container_bounds.y + PADDING,
-        };
-
-        let container = Container::Leaf {
-            bounds: container_bounds,
-            padding: PADDING,
-            window: dummy_window(WindowId::from(1u32)),
-        };
-
-        assert_eq!(container.window_bounds().unwrap(), exp_window_bounds);
-    }
+                // The original bounds (x/y) positions dependent on axis
+                let (orig_pos, _) = along(&original, axis);
+                let (original_perp_pos, _) = perpendicular(&original, axis);
 
-    #[test]
-    fn add_window_to_empty_creates_leaf() {
-        let mut container = dummy_empty();
-        let window_id = WindowId::from(1u32);
-
-        assert!(container.add_window(dummy_window(window_id), 10.0).is_ok());
-
-        match container {
-            Container::Split { children, .. } => {
-                assert_eq!(children.len(), 1);
-                match &children[0] {
-                    Container::Leaf {
-                        window,
-                        padding,
-                        bounds,
-                    } => {
-                        assert_eq!(window.id, window_id);
-                        assert_eq!(*padding, 10.0);
-                        assert_eq!(*bounds, dummy_bounds());
-                    }
-                    _ => panic!("Expected a leaf inside split"),
+                for (i, b) in out.iter().enumerate() {
+                    let (pos, size) = along(b, axis);
+                    let (perp_pos, perp_size) = perpendicular(b, axis);
+
+                    // Each child should be the same, correct size
+                    assert!(approx(size, child_share));
+                    // The dimension perpendicular to the axis we are
+                    // spreading along should just be what it was originally
+                    // (accounting for padding)
+                    assert!(approx(perp_size, expected_unchanged_dimension));
+                    // Accounting for padding, all children should have the
+                    // same (x/y) value perpendicular to the spreading axis
+                    assert!(approx(perp_pos, original_perp_pos + padding));
+                    // All children should have equally spaced including
+                    // padding in between them
+                    assert!(approx(
+                        pos,
+                        orig_pos + padding + i as f64 * (child_share + padding)
+                    ));
                 }
+
+                let covered = match axis {
+                    Vertical => out.iter().map(|b| b.height).sum(),
+                    Horizontal => out.iter().map(|b| b.width).sum(),
+                };
+                assert!(approx(covered, available_space));
             }
-            _ => panic!("Expected container to become a split"),
         }
     }
+}
 
-    #[test]
-    fn add_window_to_split_adds_new_leaf() {
-        let container_bounds = dummy_bounds();
-        let mut container = dummy_empty();
-
-        // The split should be horizontal with each leaf taking half the space.
-        let exp_first_leaf_bounds = Bounds {
-            height: container_bounds.height,
-            width: container_bounds.width / 2.0,
-            x: 0.0,
-            y: 0.0,
-        };
-        let exp_second_leaf_bounds = Bounds {
-            height: container_bounds.height,
-            width: container_bounds.width / 2.0,
-            x: container_bounds.width / 2.0,
-            y: 0.0,
-        };
-
-        let first_id = WindowId::from(1u32);
-        let second_id = WindowId::from(2u32);
-
-        const PADDING: f64 = 0.0;
+#[test]
+fn spread_bounds_along_axis_symmetry() {
+    let original = dummy_bounds();
+    let transposed = transpose(original);
 
-        container
-            .add_window(dummy_window(first_id), PADDING)
-            .unwrap();
-        container
-            .add_window(dummy_window(second_id), PADDING)
-            .unwrap();
+    let padding = 12.0;
+    let n = 4;
+
+    let h = spread_bounds_along_axis(original, Horizontal, n, padding);
+    let v = spread_bounds_along_axis(transposed, Vertical, n, padding);
+
+    for (bh, bv) in h.iter().zip(v.iter()) {
+        assert!(approx(bh.width, bv.height));
+        assert!(approx(bh.height, bv.width));
+    }
+}
 
-        match container {
-            Container::Split { children, .. } => {
-                assert_eq!(children.len(), 2);
-                let ids: Vec<_> = children
-                    .iter()
-                    .map(|c| {
-                        if let Container::Leaf { window, .. } = c {
-                            window.id
-                        } else {
-                            WindowId::from(0u32)
-                        }
-                    })
-                    .collect();
-                assert!(ids.contains(&first_id));
-                assert!(ids.contains(&second_id));
-                assert_eq!(children[0].bounds(), exp_first_leaf_bounds);
-                assert_eq!(children[1].bounds(), exp_second_leaf_bounds);
+#[test]
+fn stacked_bounds() {
+    let original = dummy_bounds();
+
+    for &padding in PADDING_VALUES {
+        for &n in CHILD_NUMS {
+            let strips = stacked_strip_bounds(original, n, padding);
+            let children = stacked_child_bounds(original, n, padding);
+            assert_eq!(strips.len(), n);
+            assert_eq!(children.len(), n);
+
+            // Strips sit one below the other from the top of the padded
+            // region, each spanning its full width.
+            for (i, strip) in strips.iter().enumerate() {
+                assert!(approx(strip.x, original.x + padding));
+                assert!(approx(strip.width, original.width - 2.0 * padding));
+                assert!(approx(strip.height, TITLE_STRIP_HEIGHT));
+                assert!(approx(
+                    strip.y,
+                    original.y + padding + i as f64 * TITLE_STRIP_HEIGHT
+                ));
+            }
+
+            // Every child fills the rest of the padded region, directly
+            // below the last strip.
+            let last_strip = &strips[n - 1];
+            for child in &children {
+                assert_eq!(child, &children[0]);
+                assert!(approx(child.x, original.x + padding));
+                assert!(approx(child.width, original.width - 2.0 * padding));
+                assert!(approx(child.y, last_strip.y + last_strip.height));
+                assert!(approx(
+                    child.y + child.height,
+                    original.y + original.height - padding
+                ));
             }
-            _ => panic!("Expected container to be a split"),
         }
     }
+}
 
-    #[test]
-    fn add_window_to_leaf_errors() {
-        let mut container = dummy_leaf(WindowId::from(1u32));
-        let result = container.add_window(dummy_window(WindowId::from(2u32)), 10.0);
-        assert!(result.is_err());
-    }
+#[test]
+fn stacked_layout_survives_add_remove_and_resize() {
+    let padding = 5.0;
+    let mut split = Split::new(dummy_bounds(), Horizontal, padding, vec![]);
+    split.set_layout(Layout::Stacked, padding).unwrap();
 
-    #[test]
-    fn splitting_empty_errors() {
-        let mut container = dummy_empty();
-        assert!(container.split(Axis::Vertical).is_err())
+    let ids: Vec<WindowId> = (1..=3u64).map(WindowId::from).collect();
+    for &id in &ids {
+        split.add_window(Window::dummy(id), padding).unwrap();
     }
 
-    #[test]
-    fn splitting_split_with_many_children_errors() {
-        // This container could not exist because the bounds are wrong
-        let mut container = dummy_split(
-            Axis::default(),
-            &[WindowId::from(1u32), WindowId::from(2u32)],
-        );
-        assert!(container.split(Axis::Vertical).is_err());
-    }
+    let assert_stacked = |split: &Split| {
+        assert_eq!(split.layout, Layout::Stacked);
+        let expected = stacked_child_bounds(split.bounds, split.children.len(), padding);
+        for (child, b) in split.children.iter().zip(expected) {
+            assert_eq!(child.bounds(), b);
+        }
+    };
+    assert_stacked(&split);
+
+    split
+        .resize(Bounds {
+            x: 100.0,
+            y: 50.0,
+            width: 1000.0,
+            height: 700.0,
+        })
+        .unwrap();
+    assert_stacked(&split);
 
-    #[test]
-    fn splitting_split_with_one_child_changes_axis() {
-        use Axis::*;
+    split.remove_window(ids[1], padding).unwrap();
+    assert_eq!(split.children.len(), 2);
+    assert_stacked(&split);
+}
 
-        for (starting_axis, change_axis, exp_axis) in [
-            (Horizontal, Vertical, Vertical),
-            (Horizontal, Horizontal, Horizontal),
-            (Vertical, Horizontal, Horizontal),
-            (Vertical, Vertical, Vertical),
-        ] {
-            let mut container = dummy_split(starting_axis, &[WindowId::from(1u32)]);
-            container.split(change_axis).unwrap();
-            assert!(
-                matches!(container, Container::Split { axis, children, .. } if axis == exp_axis && children.len() == 1)
-            );
+#[test]
+fn tabbed_bounds() {
+    let original = dummy_bounds();
+
+    for &padding in PADDING_VALUES {
+        for &n in CHILD_NUMS {
+            let children = overlap_bounds(original, n, padding);
+            assert_eq!(children.len(), n);
+
+            // Every tab fills the whole padded region.
+            for child in &children {
+                assert!(approx(child.x, original.x + padding));
+                assert!(approx(child.y, original.y + padding));
+                assert!(approx(child.width, original.width - 2.0 * padding));
+                assert!(approx(child.height, original.height - 2.0 * padding));
+            }
         }
     }
+}
 
-    #[test]
-    fn splitting_leaf_converts_to_split_with_same_bounds() {
-        let mut container = dummy_leaf(WindowId::from(1u32));
+#[test]
+fn tabbed_layout_survives_add_remove_and_resize() {
+    let padding = 5.0;
+    let mut split = Split::new(dummy_bounds(), Horizontal, padding, vec![]);
+    split.set_layout(Layout::Tabbed, padding).unwrap();
 
-        // The same as above, but we can't clone it since containers contain a
-        // Vec
-        let leaf = dummy_leaf(WindowId::from(1u32));
+    let ids: Vec<WindowId> = (1..=3u64).map(WindowId::from).collect();
+    for &id in &ids {
+        split.add_window(Window::dummy(id), padding).unwrap();
+    }
 
-        container.split(Axis::Vertical).unwrap();
+    let assert_tabbed = |split: &Split| {
+        assert_eq!(split.layout, Layout::Tabbed);
+        for child in &split.children {
+            assert_eq!(child.bounds(), split.bounds.with_pad(padding));
+        }
+    };
+    assert_tabbed(&split);
+
+    split
+        .resize(Bounds {
+            x: 100.0,
+            y: 50.0,
+            width: 1000.0,
+            height: 700.0,
+        })
+        .unwrap();
+    assert_tabbed(&split);
 
-        assert!(matches!(container, Container::Split{
-            bounds, children, axis, padding
-        } if bounds == dummy_bounds()
-            && children == vec![leaf]
-            && axis == Axis::Vertical
-            && padding == 0.0
-        ));
-    }
+    split.remove_window(ids[1], padding).unwrap();
+    assert_eq!(split.children.len(), 2);
+    assert_tabbed(&split);
+}
 
-    #[test]
-    fn find_window_empty_is_none() {
-        let container = dummy_empty();
-        assert!(container.find_window(WindowId::from(0u32)).is_none());
-    }
+fn tabbed_tree_of(ids: &[WindowId]) -> Container {
+    let mut root = tree_of(Horizontal, ids);
+    let Container::Split(split) = &mut root else {
+        unreachable!()
+    };
+    split.set_layout(Layout::Tabbed, 0.0).unwrap();
+    root
+}
 
-    #[test]
-    fn find_window_leaf() {
-        let target = WindowId::from(1u32);
-        let container = dummy_leaf(target);
+#[test]
+fn neighbouring_tab_walks_left_and_right() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let root = tabbed_tree_of(&ids);
+
+    assert_eq!(root.neighbouring_tab(ids[1], Direction::Left), Some(ids[0]));
+    assert_eq!(
+        root.neighbouring_tab(ids[1], Direction::Right),
+        Some(ids[2])
+    );
+    assert_eq!(root.neighbouring_tab(ids[0], Direction::Left), None);
+    assert_eq!(root.neighbouring_tab(ids[2], Direction::Right), None);
+    assert_eq!(root.neighbouring_tab(ids[1], Direction::Up), None);
+}
 
-        assert!(container.find_window(target).is_some());
-        assert!(container.find_window(WindowId::from(18u32)).is_none());
-    }
+#[test]
+fn neighbouring_tab_is_none_in_tiled_split() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let root = tree_of(Horizontal, &ids);
 
-    #[test]
-    fn find_window_split() {
-        let target = WindowId::from(1u32);
-        let container = dummy_split(Axis::default(), &[target]);
+    assert_eq!(root.neighbouring_tab(ids[1], Direction::Right), None);
+}
 
-        assert!(container.find_window(target).is_some());
-        assert!(container.find_window(WindowId::from(18u32)).is_none());
-    }
+#[test]
+fn visible_window_bounds_shows_only_focused_tab() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let root = tabbed_tree_of(&ids);
 
-    #[test]
-    fn window_ids_empty() {
-        let container = dummy_empty();
-        assert!(container.window_ids().is_empty());
+    for &focused in &ids {
+        let visible = root.visible_window_bounds(focused);
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible.get(&focused), Some(&root.bounds().with_pad(0.0)));
     }
+}
 
-    #[test]
-    fn window_ids_leaf() {
-        let container = dummy_leaf(WindowId::from(1u32));
-        assert_eq!(
-            container.window_ids(),
-            HashSet::from([WindowId::from(1u32)])
-        );
-    }
+#[test]
+fn weighted_bounds() {
+    let original = dummy_bounds();
+    let weights = [3.0, 1.0, 2.0];
+
+    for &padding in PADDING_VALUES {
+        for &axis in AXES {
+            let out = weighted_bounds_along_axis(original, axis, &weights, &[0.0; 3], padding);
+            assert_eq!(out.len(), weights.len());
+
+            let (orig_pos, orig_size) = along(&original, axis);
+            let available_space = orig_size - 2.0 * padding - (weights.len() - 1) as f64 * padding;
+            let total_weight: f64 = weights.iter().sum();
+
+            let mut expected_pos = orig_pos + padding;
+            for (b, weight) in out.iter().zip(weights) {
+                let (pos, size) = along(b, axis);
+                assert!(approx(pos, expected_pos));
+                assert!(approx(size, available_space * weight / total_weight));
+                expected_pos += size + padding;
+            }
 
-    #[test]
-    fn window_ids_split() {
-        let window_ids = [WindowId::from(1u32), WindowId::from(2u32)];
-        let container = dummy_split(Axis::default(), &window_ids);
-        assert_eq!(container.window_ids(), HashSet::from(window_ids));
+            let (last_pos, last_size) = along(&out[out.len() - 1], axis);
+            assert!(approx(last_pos + last_size, orig_pos + orig_size - padding));
+        }
     }
+}
 
-    #[test]
-    fn remove_window_from_leaf_non_existent() {
-        let mut container = dummy_leaf(WindowId::from(1u32));
-        let target = WindowId::from(2u32);
-        assert!(container.remove_window_from_leaf(target).unwrap().is_none());
-    }
+#[test]
+fn manual_resize_survives_add_and_remove() {
+    let padding = 0.0;
+    let (a, b, c) = (
+        WindowId::from(1u64),
+        WindowId::from(2u64),
+        WindowId::from(3u64),
+    );
+    let mut split = Split::new(dummy_bounds(), Horizontal, padding, vec![]);
+    split.add_window(Window::dummy(a), padding).unwrap();
+    split.add_window(Window::dummy(b), padding).unwrap();
+    split.move_divider(0, 50.0, 200.0, padding).unwrap();
+
+    let widths =
+        |split: &Split| -> Vec<f64> { split.children.iter().map(|c| c.bounds().width).collect() };
+    let resized = widths(&split);
+    assert!(resized[0] > resized[1]);
+
+    // The new window takes an equal share, and the existing windows keep
+    // their ratio to one another.
+    split.add_window(Window::dummy(c), padding).unwrap();
+    let added = widths(&split);
+    assert!(approx(added[0] / added[1], resized[0] / resized[1]));
+    assert!(approx(added[2], dummy_bounds().width / 3.0));
+
+    // Removing the window gives its share back.
+    split.remove_window(c, padding).unwrap();
+    let removed = widths(&split);
+    assert!(approx(removed[0], resized[0]));
+    assert!(approx(removed[1], resized[1]));
+}
 
-    #[test]
-    fn remove_window_from_leaf_existent() {
-        let mut container = dummy_leaf(WindowId::from(1u32));
-        let target = WindowId::from(1u32);
-        assert!(
-            container
-                .remove_window_from_leaf(target)
-                .unwrap()
-                .is_some_and(|id| id == target)
-        );
+fn dummy_window(id: WindowId) -> Window {
+    Window {
+        id,
+        min_width: 100.0,
+        min_height: 100.0,
     }
+}
 
-    #[test]
-    fn is_parent_leaf_non_leaf() {
-        let empty = dummy_empty();
-        let split = dummy_split(
-            Axis::default(),
-            &[WindowId::from(1u32), WindowId::from(2u32)],
-        );
+fn dummy_empty_split(bounds: Bounds) -> Container {
+    Container::Split(Split::new(bounds, Axis::default(), 0.0, vec![]))
+}
 
-        assert!(!empty.is_parent_leaf(WindowId::from(1u32)));
-        assert!(!split.is_parent_leaf(WindowId::from(2u32)));
+// Describe the shape of a tree, e.g. `H[1,V[2,3]]`, so that the result of
+// rearranging it can be asserted on in one go.
+fn shape(container: &Container) -> String {
+    match container {
+        Container::Leaf(leaf) => leaf.window.id.to_string(),
+        Container::Split(split) => {
+            let axis = match split.axis {
+                Horizontal => "H",
+                Vertical => "V",
+            };
+            let children: Vec<_> = split.children.iter().map(shape).collect();
+            format!("{axis}[{}]", children.join(","))
+        }
     }
+}
 
-    #[test]
-    fn is_parent_leaf_leaf() {
-        let target = WindowId::from(1u32);
-        let parent = dummy_leaf(target);
-        let non_parent = dummy_leaf(WindowId::from(2u32));
+#[test]
+fn get_bounds() {
+    let leaf = Leaf::dummy(&WindowId::from(1u32));
+    let split = Split::dummy(Axis::default(), &[WindowId::from(1u32)]);
 
-        assert!(parent.is_parent_leaf(target));
-        assert!(!non_parent.is_parent_leaf(target));
-    }
+    assert_eq!(leaf.bounds(), dummy_bounds());
+    assert_eq!(split.bounds(), dummy_bounds());
+}
 
-    #[test]
-    fn remove_window_empty() {
-        assert!(
-            dummy_empty()
-                .remove_window(WindowId::from(1u32), 0.0)
-                .unwrap()
-                .is_none()
-        )
-    }
+#[test]
+fn contains_window() {
+    let target = WindowId::from(1u32);
+    let leaf_with = Leaf::dummy(&target);
+    let leaf_without = Leaf::dummy(&WindowId::from(2u32));
+    let split_with = Split::dummy(Horizontal, &[target]);
+    let split_without = Split::dummy(Horizontal, &[WindowId::from(2u32)]);
+
+    assert!(leaf_with.contains_window(target));
+    assert!(!leaf_without.contains_window(target));
+    assert!(split_with.contains_window(target));
+    assert!(!split_without.contains_window(target));
+}
+
+#[test]
+fn add_window_to_empty_split_creates_leaf() {
+    let mut container = dummy_empty_split(dummy_bounds());
+    let window_id = WindowId::from(1u32);
+
+    assert!(container.add_window(dummy_window(window_id), 10.0).is_ok());
+
+    let Container::Split(split) = container else {
+        panic!("Expected container to remain a split");
+    };
+    assert_eq!(split.children.len(), 1);
+    let Container::Leaf(leaf) = &split.children[0] else {
+        panic!("Expected a leaf inside split");
+    };
+    assert_eq!(leaf.window.id, window_id);
+    assert_eq!(leaf.padding, 10.0);
+    assert_eq!(leaf.bounds, dummy_bounds().with_pad(10.0));
+}
 
-    #[test]
-    fn remove_window_leaf_target_exists() {
-        let target = WindowId::from(1u32);
-        let mut leaf = dummy_leaf(target);
+#[test]
+fn add_window_to_split_adds_new_leaf() {
+    let container_bounds = dummy_bounds();
+    let mut container = dummy_empty_split(container_bounds);
+
+    // The split should be horizontal with each leaf taking half the space.
+    let exp_first_leaf_bounds = Bounds {
+        height: container_bounds.height,
+        width: container_bounds.width / 2.0,
+        x: 0.0,
+        y: 0.0,
+    };
+    let exp_second_leaf_bounds = Bounds {
+        height: container_bounds.height,
+        width: container_bounds.width / 2.0,
+        x: container_bounds.width / 2.0,
+        y: 0.0,
+    };
+
+    let first_id = WindowId::from(1u32);
+    let second_id = WindowId::from(2u32);
+
+    const PADDING: f64 = 0.0;
+
+    container
+        .add_window(dummy_window(first_id), PADDING)
+        .unwrap();
+    container
+        .add_window(dummy_window(second_id), PADDING)
+        .unwrap();
 
-        let res = leaf.remove_window(target, 0.0).unwrap();
+    let Container::Split(split) = container else {
+        panic!("Expected container to be a split");
+    };
+    assert_eq!(split.children.len(), 2);
+    assert_eq!(split.window_ids(), HashSet::from([first_id, second_id]));
+    assert_eq!(split.children[0].bounds(), exp_first_leaf_bounds);
+    assert_eq!(split.children[1].bounds(), exp_second_leaf_bounds);
+}
 
-        assert!(res.is_some_and(|id| id == target));
-        assert!(matches!(leaf, Container::Empty { .. }))
-    }
+#[test]
+fn add_window_to_leaf_errors() {
+    let mut container = Leaf::dummy(&WindowId::from(1u32));
+    let result = container.add_window(dummy_window(WindowId::from(2u32)), 10.0);
+    assert!(result.is_err());
+}
 
-    #[test]
-    fn remove_window_leaf_target_does_not_exist() {
-        let target = WindowId::from(1u32);
-        let mut leaf = dummy_leaf(WindowId::from(2u32));
+#[test]
+fn splitting_empty_split_changes_axis() {
+    let mut container = dummy_empty_split(dummy_bounds());
+    container.split(Vertical).unwrap();
+    assert!(
+        matches!(container, Container::Split(split) if split.axis == Vertical && split.children.is_empty())
+    );
+}
 
-        let res = leaf.remove_window(target, 0.0).unwrap();
+#[test]
+fn splitting_split_with_many_children_errors() {
+    // This container could not exist because the bounds are wrong
+    let mut container = Split::dummy(
+        Axis::default(),
+        &[WindowId::from(1u32), WindowId::from(2u32)],
+    );
+    assert!(container.split(Vertical).is_err());
+}
 
-        assert!(res.is_none());
-        assert!(matches!(leaf, Container::Leaf { window,.. } if window.id == WindowId::from(2u32)));
+#[test]
+fn splitting_split_with_one_child_changes_axis() {
+    for (starting_axis, change_axis, exp_axis) in [
+        (Horizontal, Vertical, Vertical),
+        (Horizontal, Horizontal, Horizontal),
+        (Vertical, Horizontal, Horizontal),
+        (Vertical, Vertical, Vertical),
+    ] {
+        let mut container = Split::dummy(starting_axis, &[WindowId::from(1u32)]);
+        container.split(change_axis).unwrap();
+        assert!(
+            matches!(container, Container::Split(split) if split.axis == exp_axis && split.children.len() == 1)
+        );
     }
+}
 
-    #[test]
-    fn remove_window_split_target_does_not_exist() {
-        let target = WindowId::from(1u32);
-        let mut split = dummy_split(Axis::default(), &[WindowId::from(2u32)]);
+#[test]
+fn splitting_leaf_converts_to_split_with_same_bounds() {
+    let mut container = Leaf::dummy(&WindowId::from(1u32));
 
-        let res = split.remove_window(target, 0.0).unwrap();
-        assert!(res.is_none())
-    }
+    // The same as above, but we can't clone it since containers contain a
+    // Vec
+    let leaf = Leaf::dummy(&WindowId::from(1u32));
 
-    #[test]
-    fn remove_window_split_target_exists_only_child() {
-        let target = WindowId::from(1u32);
-        let mut split = dummy_split(Axis::default(), &[target]);
+    container.split(Vertical).unwrap();
 
-        let res = split.remove_window(target, 0.0).unwrap();
+    assert!(matches!(container, Container::Split(split)
+        if split.bounds == dummy_bounds()
+            && split.children == vec![leaf]
+            && split.axis == Vertical
+            && split.padding == 0.0
+    ));
+}
 
-        assert!(res.is_some_and(|id| id == target));
-        assert!(matches!(split, Container::Empty { .. }))
-    }
+#[test]
+fn find_window_leaf() {
+    let target = WindowId::from(1u32);
+    let container = Leaf::dummy(&target);
 
-    #[test]
-    fn remove_window_split_target_exists_two_children() {
-        let target = WindowId::from(1u32);
-        let mut split = dummy_split(
-            Axis::default(),
-            &[WindowId::from(1u32), WindowId::from(2u32)],
-        );
+    assert!(container.find_window(target).is_some());
+    assert!(container.find_window(WindowId::from(18u32)).is_none());
+}
 
-        let res = split.remove_window(target, 0.0).unwrap();
+#[test]
+fn find_window_split() {
+    let target = WindowId::from(1u32);
+    let container = Split::dummy(Axis::default(), &[target]);
 
-        assert!(res.is_some_and(|id| id == target));
-        assert!(matches!(split, Container::Split { children, .. }
-            if children == vec![Container::Leaf {
-                bounds: split.bounds(),
-                padding: 0.0,
-                window: dummy_window(WindowId::from(2u32)),
-            }]
-        ))
-    }
+    assert!(container.find_window(target).is_some());
+    assert!(container.find_window(WindowId::from(18u32)).is_none());
+}
 
-    #[test]
-    fn remove_window_split_target_exists_many_children() {
-        let target = WindowId::from(1u32);
-        let mut split = dummy_split(
-            Axis::default(),
-            &[
-                WindowId::from(1u32),
-                WindowId::from(2u32),
-                WindowId::from(3u32),
-            ],
-        );
+#[test]
+fn find_window_empty_split_is_none() {
+    let container = dummy_empty_split(dummy_bounds());
+    assert!(container.find_window(WindowId::from(0u32)).is_none());
+}
 
-        let res = split.remove_window(target, 0.0).unwrap();
-
-        let exp_child_bounds = spread_bounds_along_axis(split.bounds(), Axis::default(), 2, 0.0);
-
-        assert!(res.is_some_and(|id| id == target));
-        assert!(matches!(split, Container::Split { children, .. }
-            if children == vec![
-                Container::Leaf {
-                    bounds: exp_child_bounds[0],
-                    padding: 0.0,
-                    window:dummy_window(WindowId::from(2u32))
-                },
-                Container::Leaf {
-                    bounds:exp_child_bounds[1],
-                    padding: 0.0,
-                    window:dummy_window(WindowId::from(3u32))
-                }
-            ]
-        ))
-    }
+#[test]
+fn window_bounds_by_id_empty_split_is_empty() {
+    let container = dummy_empty_split(dummy_bounds());
+    assert!(container.window_bounds_by_id().is_empty());
+}
 
-    #[test]
-    fn get_leaf_of_window_mut_empty() {
-        let mut container = dummy_empty();
-        let target = WindowId::from(1u32);
+#[test]
+fn window_bounds_by_id_includes_correct_padding() {
+    const PADDING: f64 = 10.0;
+    let target = WindowId::from(1u32);
+    let mut container = Container::Split(Split::new(dummy_bounds(), Horizontal, PADDING, vec![]));
+    container.add_window(dummy_window(target), PADDING).unwrap();
+
+    assert_eq!(
+        container.window_bounds_by_id(),
+        HashMap::from([(target, dummy_bounds().with_pad(PADDING))])
+    );
+}
 
-        assert!(container.parent_leaf_of_window_mut(target).is_none());
-    }
+#[test]
+fn window_ids_empty_split() {
+    let container = dummy_empty_split(dummy_bounds());
+    assert!(container.window_ids().is_empty());
+}
 
-    #[test]
-    fn get_leaf_of_window_mut_leaf() {
-        let target = WindowId::from(1u32);
-        let mut is_parent = dummy_leaf(target);
-        let mut not_parent = dummy_leaf(WindowId::from(2u32));
+#[test]
+fn window_ids_leaf() {
+    let container = Leaf::dummy(&WindowId::from(1u32));
+    assert_eq!(
+        container.window_ids(),
+        HashSet::from([WindowId::from(1u32)])
+    );
+}
 
-        assert!(is_parent.parent_leaf_of_window_mut(target).is_some_and(
-            |leaf| matches!(leaf, Container::Leaf { window,.. } if window.id == target)
-        ));
+#[test]
+fn window_ids_split() {
+    let window_ids = [WindowId::from(1u32), WindowId::from(2u32)];
+    let container = Split::dummy(Axis::default(), &window_ids);
+    assert_eq!(container.window_ids(), HashSet::from(window_ids));
+}
 
-        assert!(not_parent.parent_leaf_of_window_mut(target).is_none());
-    }
+#[test]
+fn remove_window_empty_split() {
+    assert!(matches!(
+        dummy_empty_split(dummy_bounds())
+            .remove_window(WindowId::from(1u32), 0.0)
+            .unwrap(),
+        RemoveResult::NotFound
+    ));
+}
 
-    #[test]
-    fn get_leaf_of_window_split() {
-        let target = WindowId::from(1u32);
-        let mut exists = dummy_split(Axis::default(), &[WindowId::from(2u32), target]);
-        let mut doest_not_exist = dummy_split(
-            Axis::default(),
-            &[WindowId::from(2u32), WindowId::from(3u32)],
-        );
+#[test]
+fn remove_window_leaf_target_exists() {
+    let target = WindowId::from(1u32);
+    let mut leaf = Leaf::dummy(&target);
 
-        assert!(exists.parent_leaf_of_window_mut(target).is_some());
-        assert!(doest_not_exist.parent_leaf_of_window_mut(target).is_none());
-    }
+    let res = leaf.remove_window(target, 0.0).unwrap();
 
-    #[test]
-    fn spread_bounds_along_axis_horizontal() {
-        let original = dummy_bounds();
+    assert!(matches!(res, RemoveResult::BecomeEmpty));
+}
 
-        for &padding in &[0.0, 5.0, 10.0, 17.5] {
-            for n in 1usize..=8 {
-                let out = spread_bounds_along_axis(original, Axis::Horizontal, n, padding);
-                assert_eq!(out.len(), n);
+#[test]
+fn remove_window_leaf_target_does_not_exist() {
+    let target = WindowId::from(1u32);
+    let mut leaf = Leaf::dummy(&WindowId::from(2u32));
 
-                let total_inner_gap = (n - 1) as f64 * padding;
-                let available_width = original.width - 2.0 * padding - total_inner_gap;
-                let child_width = available_width / n as f64;
-                let expected_height = original.height - 2.0 * padding;
+    let res = leaf.remove_window(target, 0.0).unwrap();
 
-                // The first child starts after the correct padding
-                assert!(approx(out[0].x, original.x + padding));
-                assert!(approx(out[0].y, original.y + padding));
+    assert!(matches!(res, RemoveResult::NotFound));
+    assert!(matches!(leaf, Container::Leaf(leaf) if leaf.window.id == WindowId::from(2u32)));
+}
 
-                // The last child has the correct amount of padding after it
-                let last = &out[n - 1];
-                assert!(approx(
-                    last.x + last.width,
-                    original.x + original.width - padding
-                ));
+#[test]
+fn parent_leaf_of_window_empty_split_is_none() {
+    let mut container = dummy_empty_split(dummy_bounds());
+    assert!(
+        container
+            .parent_leaf_of_window_mut(WindowId::from(1u32))
+            .is_none()
+    );
+}
 
-                // Each inner child's bounds are correct
-                for (i, b) in out.iter().enumerate() {
-                    assert!(approx(b.width, child_width));
-                    assert!(approx(b.height, expected_height));
-                    assert!(approx(b.y, original.y + padding));
+#[test]
+fn parent_leaf_of_window_leaf() {
+    let target = WindowId::from(1u32);
+    let mut parent = Leaf::dummy(&target);
+    let mut non_parent = Leaf::dummy(&WindowId::from(2u32));
 
-                    let expected_x = original.x + padding + i as f64 * (child_width + padding);
-                    assert!(approx(b.x, expected_x));
-                }
+    assert!(parent.parent_leaf_of_window_mut(target).is_some());
+    assert!(non_parent.parent_leaf_of_window_mut(target).is_none());
+}
 
-                // The difference between child positions is the padding
-                for i in 1..n {
-                    let prev = &out[i - 1];
-                    let cur = &out[i];
-                    assert!(approx(cur.x - (prev.x + prev.width), padding));
-                }
+#[test]
+fn remove_window_split_target_does_not_exist() {
+    let target = WindowId::from(1u32);
+    let mut split = Split::dummy(Axis::default(), &[WindowId::from(2u32)]);
 
-                // Validate that the bounds aren't overlapping (bar some margin
-                // for error)
-                for i in 1..n {
-                    assert!(out[i].x >= out[i - 1].x + out[i - 1].width - EPSILON);
-                }
+    let res = split.remove_window(target, 0.0).unwrap();
+    assert!(matches!(res, RemoveResult::NotFound));
+}
 
-                // Full available width is spanned
-                let covered: f64 = out.iter().map(|b| b.width).sum();
-                assert!(approx(covered, available_width));
-            }
-        }
-    }
+#[test]
+fn remove_window_split_target_exists_only_child() {
+    let target = WindowId::from(1u32);
+    let mut split = Split::dummy(Axis::default(), &[target]);
 
-    #[test]
-    fn spread_bounds_along_axis_vertical() {
-        let original = dummy_bounds();
+    let res = split.remove_window(target, 0.0).unwrap();
 
-        for &padding in &[0.0, 3.0, 8.0, 20.0] {
-            for n in 1usize..=8 {
-                let out = spread_bounds_along_axis(original, Axis::Vertical, n, padding);
+    assert!(matches!(res, RemoveResult::BecomeEmpty));
+}
 
-                assert_eq!(out.len(), n);
+#[test]
+fn remove_window_split_target_exists_two_children() {
+    let target = WindowId::from(1u32);
+    let mut split = Split::dummy(
+        Axis::default(),
+        &[WindowId::from(1u32), WindowId::from(2u32)],
+    );
+
+    let res = split.remove_window(target, 0.0).unwrap();
+
+    assert!(matches!(res, RemoveResult::Removed));
+    let bounds = split.bounds();
+    assert!(matches!(split, Container::Split(split)
+        if split.children == vec![Container::Leaf(Leaf::new(
+            bounds,
+            0.0,
+            dummy_window(WindowId::from(2u32)),
+        ))]
+    ))
+}
 
-                let total_inner_gap = (n - 1) as f64 * padding;
-                let available_height = original.height - 2.0 * padding - total_inner_gap;
-                let child_height = available_height / n as f64;
-                let expected_width = original.width - 2.0 * padding;
+#[test]
+fn remove_window_split_target_exists_many_children() {
+    let target = WindowId::from(1u32);
+    let mut split = Split::dummy(
+        Axis::default(),
+        &[
+            WindowId::from(1u32),
+            WindowId::from(2u32),
+            WindowId::from(3u32),
+        ],
+    );
+
+    let res = split.remove_window(target, 0.0).unwrap();
+
+    let exp_child_bounds = spread_bounds_along_axis(split.bounds(), Axis::default(), 2, 0.0);
+
+    assert!(matches!(res, RemoveResult::Removed));
+    assert!(matches!(split, Container::Split(split)
+        if split.children == vec![
+            Container::Leaf(Leaf::new(exp_child_bounds[0], 0.0, dummy_window(WindowId::from(2u32)))),
+            Container::Leaf(Leaf::new(exp_child_bounds[1], 0.0, dummy_window(WindowId::from(3u32)))),
+        ]
+    ))
+}
 
-                assert!(approx(out[0].y, original.y + padding));
-                assert!(approx(out[0].x, original.x + padding));
+#[test]
+fn get_leaf_of_window_mut_leaf() {
+    let target = WindowId::from(1u32);
+    let mut is_parent = Leaf::dummy(&target);
+    let mut not_parent = Leaf::dummy(&WindowId::from(2u32));
 
-                let last = &out[n - 1];
-                assert!(approx(
-                    last.y + last.height,
-                    original.y + original.height - padding
-                ));
+    assert!(
+        is_parent
+            .parent_leaf_of_window_mut(target)
+            .is_some_and(|leaf| matches!(leaf, Container::Leaf(leaf) if leaf.window.id == target))
+    );
 
-                for (i, b) in out.iter().enumerate() {
-                    assert!(approx(b.height, child_height));
-                    assert!(approx(b.width, expected_width));
-                    assert!(approx(b.x, original.x + padding));
+    assert!(not_parent.parent_leaf_of_window_mut(target).is_none());
+}
 
-                    let expected_y = original.y + padding + i as f64 * (child_height + padding);
-                    assert!(approx(b.y, expected_y));
-                }
+#[test]
+fn get_leaf_of_window_split() {
+    let target = WindowId::from(1u32);
+    let mut exists = Split::dummy(Axis::default(), &[WindowId::from(2u32), target]);
+    let mut doest_not_exist = Split::dummy(
+        Axis::default(),
+        &[WindowId::from(2u32), WindowId::from(3u32)],
+    );
+
+    assert!(exists.parent_leaf_of_window_mut(target).is_some());
+    assert!(doest_not_exist.parent_leaf_of_window_mut(target).is_none());
+}
 
-                for i in 1..n {
-                    let prev = &out[i - 1];
-                    let cur = &out[i];
-                    assert!(approx(cur.y - (prev.y + prev.height), padding));
-                }
+#[test]
+fn spread_bounds_along_axis_horizontal() {
+    let original = dummy_bounds();
+
+    for &padding in &[0.0, 5.0, 10.0, 17.5] {
+        for n in 1usize..=8 {
+            let out = spread_bounds_along_axis(original, Horizontal, n, padding);
+            assert_eq!(out.len(), n);
+
+            let total_inner_gap = (n - 1) as f64 * padding;
+            let available_width = original.width - 2.0 * padding - total_inner_gap;
+            let child_width = available_width / n as f64;
+            let expected_height = original.height - 2.0 * padding;
+
+            // The first child starts after the correct padding
+            assert!(approx(out[0].x, original.x + padding));
+            assert!(approx(out[0].y, original.y + padding));
+
+            // The last child has the correct amount of padding after it
+            let last = &out[n - 1];
+            assert!(approx(
+                last.x + last.width,
+                original.x + original.width - padding
+            ));
+
+            // Each inner child's bounds are correct
+            for (i, b) in out.iter().enumerate() {
+                assert!(approx(b.width, child_width));
+                assert!(approx(b.height, expected_height));
+                assert!(approx(b.y, original.y + padding));
 
-                for i in 1..n {
-                    assert!(out[i].y >= out[i - 1].y + out[i - 1].height - EPSILON);
-                }
+                let expected_x = original.x + padding + i as f64 * (child_width + padding);
+                assert!(approx(b.x, expected_x));
+            }
+
+            // The difference between child positions is the padding
+            for i in 1..n {
+                let prev = &out[i - 1];
+                let cur = &out[i];
+                assert!(approx(cur.x - (prev.x + prev.width), padding));
+            }
 
-                let covered: f64 = out.iter().map(|b| b.height).sum();
-                assert!(approx(covered, available_height));
+            // Validate that the bounds aren't overlapping (bar some margin
+            // for error)
+            for i in 1..n {
+                assert!(out[i].x >= out[i - 1].x + out[i - 1].width - EPSILON);
             }
+
+            // Full available width is spanned
+            let covered: f64 = out.iter().map(|b| b.width).sum();
+            assert!(approx(covered, available_width));
         }
     }
+}
+
+#[test]
+fn spread_bounds_along_axis_vertical() {
+    let original = dummy_bounds();
+
+    for &padding in &[0.0, 3.0, 8.0, 20.0] {
+        for n in 1usize..=8 {
+            let out = spread_bounds_along_axis(original, Vertical, n, padding);
 
-    #[test]
-    fn n_equals_one_fills_inner_area() {
-        let original = dummy_bounds();
+            assert_eq!(out.len(), n);
 
-        for &padding in &[0.0, 5.0, 25.0] {
-            for axis in [Axis::Horizontal, Axis::Vertical] {
-                let out = spread_bounds_along_axis(original, axis, 1, padding);
-                let b = &out[0];
+            let total_inner_gap = (n - 1) as f64 * padding;
+            let available_height = original.height - 2.0 * padding - total_inner_gap;
+            let child_height = available_height / n as f64;
+            let expected_width = original.width - 2.0 * padding;
 
+            assert!(approx(out[0].y, original.y + padding));
+            assert!(approx(out[0].x, original.x + padding));
+
+            let last = &out[n - 1];
+            assert!(approx(
+                last.y + last.height,
+                original.y + original.height - padding
+            ));
+
+            for (i, b) in out.iter().enumerate() {
+                assert!(approx(b.height, child_height));
+                assert!(approx(b.width, expected_width));
                 assert!(approx(b.x, original.x + padding));
-                assert!(approx(b.y, original.y + padding));
-                assert!(approx(b.width, original.width - 2.0 * padding));
-                assert!(approx(b.height, original.height - 2.0 * padding));
+
+                let expected_y = original.y + padding + i as f64 * (child_height + padding);
+                assert!(approx(b.y, expected_y));
             }
-        }
-    }
 
-    #[test]
-    fn spread_bounds_along_axis_symmetry() {
-        let original = Bounds {
-            x: 0.0,
-            y: 0.0,
-            width: 600.0,
-            height: 800.0,
-        };
+            for i in 1..n {
+                let prev = &out[i - 1];
+                let cur = &out[i];
+                assert!(approx(cur.y - (prev.y + prev.height), padding));
+            }
 
-        let padding = 12.0;
-        let n = 4;
+            for i in 1..n {
+                assert!(out[i].y >= out[i - 1].y + out[i - 1].height - EPSILON);
+            }
 
-        let h = spread_bounds_along_axis(original, Axis::Horizontal, n, padding);
+            let covered: f64 = out.iter().map(|b| b.height).sum();
+            assert!(approx(covered, available_height));
+        }
+    }
+}
 
-        let transposed = Bounds {
-            x: original.x,
-            y: original.y,
-            width: original.height,
-            height: original.width,
-        };
+#[test]
+fn n_equals_one_fills_inner_area() {
+    let original = dummy_bounds();
 
-        let v = spread_bounds_along_axis(transposed, Axis::Vertical, n, padding);
+    for &padding in &[0.0, 5.0, 25.0] {
+        for axis in [Horizontal, Vertical] {
+            let out = spread_bounds_along_axis(original, axis, 1, padding);
+            let b = &out[0];
 
-        for (bh, bv) in h.iter().zip(v.iter()) {
-            assert!(approx(bh.width, bv.height));
-            assert!(approx(bh.height, bv.width));
+            assert!(approx(b.x, original.x + padding));
+            assert!(approx(b.y, original.y + padding));
+            assert!(approx(b.width, original.width - 2.0 * padding));
+            assert!(approx(b.height, original.height - 2.0 * padding));
         }
     }
+}
 
-    fn two_window_split() -> (Container, WindowId, WindowId) {
-        let mut root = Container::Empty {
-            bounds: Bounds {
-                x: 0.0,
-                y: 0.0,
-                width: 900.0,
-                height: 600.0,
-            },
-        };
-        let a = WindowId::from(1u32);
-        let b = WindowId::from(2u32);
+fn wide_bounds() -> Bounds {
+    Bounds {
+        x: 0.0,
+        y: 0.0,
+        width: 900.0,
+        height: 600.0,
+    }
+}
+
+fn tree_of(axis: Axis, ids: &[WindowId]) -> Container {
+    let mut root = Container::Split(Split::new(wide_bounds(), axis, 0.0, vec![]));
+    for &id in ids {
         root.add_window(
             Window {
-                id: a,
+                id,
                 min_width: 0.0,
                 min_height: 0.0,
             },
             0.0,
         )
         .unwrap();
-        root.add_window(
-            Window {
-                id: b,
-                min_width: 0.0,
-                min_height: 0.0,
-            },
-            0.0,
-        )
+    }
+    root
+}
+
+fn two_window_split() -> (Container, WindowId, WindowId) {
+    let a = WindowId::from(1u32);
+    let b = WindowId::from(2u32);
+    (tree_of(Horizontal, &[a, b]), a, b)
+}
+
+/// After a nested split's only window is removed, the sibling of that nested
+/// split should expand to fill the whole parent — not keep its old half-width.
+///
+/// Scenario
+/// --------
+///   root (H-split, 900 wide)
+///   ├── leaf A  (450 wide)          ← stays
+///   └── inner (H-split, 450 wide)
+///       └── leaf B  (450 wide)     ← removed; inner becomes empty
+///
+/// After removal of B:
+///   root should be left with A alone, and A's bounds should span the full
+///   900 width.
+#[test]
+fn remove_nested_window_rebalances_parent() {
+    let (mut root, a, b) = two_window_split();
+
+    // Split leaf B into its own inner split (simulates user pressing split).
+    // parent_leaf_of_window_mut returns the Leaf itself; calling split() on it
+    // converts it to a Split containing that leaf.
+    root.parent_leaf_of_window_mut(b)
+        .unwrap()
+        .split(Horizontal)
+        .unwrap();
+
+    // Sanity: both windows still present.
+    assert!(root.contains_window(a));
+    assert!(root.contains_window(b));
+
+    // Remove B — it now lives inside the inner split.
+    let removed = root.remove_window(b, 0.0).unwrap();
+    assert!(
+        matches!(removed, RemoveResult::Removed),
+        "B must be reported as removed"
+    );
+    assert!(!root.contains_window(b));
+
+    // A must still exist.
+    assert!(root.contains_window(a));
+
+    // A's bounds must now span the full 900 px, not stay at the old 450.
+    let bounds_map = root.window_bounds_by_id();
+    let a_bounds = bounds_map[&a];
+    assert!(
+        approx(a_bounds.width, 900.0),
+        "after nested removal, surviving window must expand to full width; got {}",
+        a_bounds.width
+    );
+}
+
+/// Simpler case: direct removal from a two-child split still works after the
+/// refactor (regression guard for the direct path).
+#[test]
+fn remove_direct_child_rebalances_sibling() {
+    let (mut root, a, b) = two_window_split();
+
+    root.remove_window(a, 0.0).unwrap();
+
+    assert!(!root.contains_window(a));
+    assert!(root.contains_window(b));
+
+    let bounds_map = root.window_bounds_by_id();
+    let b_bounds = bounds_map[&b];
+    assert!(
+        approx(b_bounds.width, 900.0),
+        "surviving window must fill full width after direct removal; got {}",
+        b_bounds.width
+    );
+}
+
+/// Three-window split: removing the middle window rebalances the outer two to
+/// each take half the space (no ghost third slot).
+#[test]
+fn remove_middle_of_three_no_ghost_slot() {
+    let a = WindowId::from(1u32);
+    let b = WindowId::from(2u32);
+    let c = WindowId::from(3u32);
+    let mut root = tree_of(Horizontal, &[a, b, c]);
+
+    root.remove_window(b, 0.0).unwrap();
+
+    let bounds_map = root.window_bounds_by_id();
+    assert!(approx(bounds_map[&a].width, 450.0));
+    assert!(approx(bounds_map[&c].width, 450.0));
+    // The two survivors must not overlap and must tile perfectly.
+    assert!(approx(bounds_map[&a].x, 0.0));
+    assert!(approx(bounds_map[&c].x, 450.0));
+}
+
+// Build `H[1,V[2,3]]`: window 1 beside a vertical split holding 2 above 3.
+fn tree_with_nested_vertical() -> Container {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids[..2]);
+
+    root.parent_leaf_of_window_mut(ids[1])
+        .unwrap()
+        .split(Vertical)
+        .unwrap();
+    root.get_parent_of_window_mut(ids[1])
+        .unwrap()
+        .add_window(dummy_window(ids[2]), 0.0)
         .unwrap();
-        (root, a, b)
+
+    assert_eq!(shape(&root), "H[1,V[2,3]]");
+    root
+}
+
+fn move_window(root: &mut Container, id: u32, direction: Direction) -> MoveResult {
+    root.move_container(WindowId::from(id), 0, direction, 0.0)
+        .unwrap()
+}
+
+#[test]
+fn move_swaps_with_sibling_leaf() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids);
+
+    assert_eq!(
+        move_window(&mut root, 2, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(shape(&root), "H[1,3,2]");
+
+    assert_eq!(
+        move_window(&mut root, 2, Direction::Left),
+        MoveResult::Moved
+    );
+    assert_eq!(
+        move_window(&mut root, 2, Direction::Left),
+        MoveResult::Moved
+    );
+    assert_eq!(shape(&root), "H[2,1,3]");
+
+    // The bounds follow the new order.
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&ids[1]].x, 0.0));
+    assert!(approx(bounds[&ids[0]].x, 300.0));
+    assert!(approx(bounds[&ids[2]].x, 600.0));
+}
+
+#[test]
+fn move_swap_keeps_weights_with_their_windows() {
+    let (mut root, a, _) = two_window_split();
+    root.resize_container(a, 0, Direction::Right, 50.0, 200.0, 0.0)
+        .unwrap();
+    let a_width = root.window_bounds_by_id()[&a].width;
+
+    move_window(&mut root, 1, Direction::Right);
+
+    assert_eq!(shape(&root), "H[2,1]");
+    assert!(approx(root.window_bounds_by_id()[&a].width, a_width));
+}
+
+#[test]
+fn move_at_edge_of_matching_root_does_nothing() {
+    let (mut root, _, _) = two_window_split();
+
+    assert_eq!(
+        move_window(&mut root, 2, Direction::Right),
+        MoveResult::AtEdge
+    );
+    assert_eq!(
+        move_window(&mut root, 1, Direction::Left),
+        MoveResult::AtEdge
+    );
+    assert_eq!(shape(&root), "H[1,2]");
+}
+
+#[test]
+fn move_across_root_axis_wraps_root() {
+    let (mut root, a, b) = two_window_split();
+
+    assert_eq!(move_window(&mut root, 1, Direction::Up), MoveResult::Moved);
+    assert_eq!(shape(&root), "V[1,2]");
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&a].y, 0.0));
+    assert!(approx(bounds[&a].width, 900.0));
+    assert!(approx(bounds[&b].y, 300.0));
+}
+
+#[test]
+fn move_only_window_does_nothing() {
+    let mut root = tree_of(Horizontal, &[WindowId::from(1u32)]);
+
+    assert_eq!(move_window(&mut root, 1, Direction::Up), MoveResult::AtEdge);
+    assert_eq!(shape(&root), "H[1]");
+}
+
+#[test]
+fn move_into_neighbouring_split() {
+    let mut root = tree_with_nested_vertical();
+
+    // The neighbour is vertical, so the window joins the end of it, leaving
+    // the root with a single split to take on.
+    assert_eq!(
+        move_window(&mut root, 1, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(shape(&root), "V[2,3,1]");
+}
+
+#[test]
+fn move_into_neighbouring_split_of_same_orientation_lands_at_near_end() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids[..2]);
+    root.parent_leaf_of_window_mut(ids[1])
+        .unwrap()
+        .split(Horizontal)
+        .unwrap();
+    root.get_parent_of_window_mut(ids[1])
+        .unwrap()
+        .add_window(dummy_window(ids[2]), 0.0)
+        .unwrap();
+    assert_eq!(shape(&root), "H[1,H[2,3]]");
+
+    assert_eq!(
+        move_window(&mut root, 1, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(shape(&root), "H[1,2,3]");
+}
+
+#[test]
+fn move_out_of_nested_split_into_ancestor() {
+    let mut root = tree_with_nested_vertical();
+
+    // 3 is at the right edge of its vertical split, so it lands to the right
+    // of that split in the horizontal root.
+    assert_eq!(
+        move_window(&mut root, 3, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(shape(&root), "H[1,2,3]");
+
+    let mut root = tree_with_nested_vertical();
+
+    // Likewise moving left places it between 1 and the vertical split.
+    assert_eq!(
+        move_window(&mut root, 2, Direction::Left),
+        MoveResult::Moved
+    );
+    assert_eq!(shape(&root), "H[1,2,3]");
+}
+
+#[test]
+fn move_within_nested_split_along_its_axis() {
+    let mut root = tree_with_nested_vertical();
+
+    assert_eq!(
+        move_window(&mut root, 2, Direction::Down),
+        MoveResult::Moved
+    );
+    assert_eq!(shape(&root), "H[1,V[3,2]]");
+
+    // At the bottom of the vertical split, and no vertical ancestor to move
+    // into, so the root is wrapped.
+    assert_eq!(
+        move_window(&mut root, 2, Direction::Down),
+        MoveResult::Moved
+    );
+    assert_eq!(shape(&root), "V[H[1,3],2]");
+}
+
+#[test]
+fn moving_last_window_out_of_nested_split_removes_it() {
+    let ids: Vec<WindowId> = (1..=2u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids);
+    root.parent_leaf_of_window_mut(ids[1])
+        .unwrap()
+        .split(Vertical)
+        .unwrap();
+    assert_eq!(shape(&root), "H[1,V[2]]");
+
+    assert_eq!(
+        move_window(&mut root, 2, Direction::Left),
+        MoveResult::Moved
+    );
+    assert_eq!(shape(&root), "H[1,2]");
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&ids[0]].width, 450.0));
+    assert!(approx(bounds[&ids[1]].x, 450.0));
+}
+
+#[test]
+fn depth_and_selection_follow_the_tree() {
+    let root = tree_with_nested_vertical();
+    let (a, c) = (WindowId::from(1u32), WindowId::from(3u32));
+
+    assert_eq!(root.depth_of_window(a), Some(1));
+    assert_eq!(root.depth_of_window(c), Some(2));
+    assert_eq!(root.depth_of_window(WindowId::from(18u32)), None);
+
+    assert_eq!(shape(root.selected(c, 0).unwrap()), "3");
+    assert_eq!(shape(root.selected(c, 1).unwrap()), "V[2,3]");
+    assert_eq!(shape(root.selected(c, 2).unwrap()), "H[1,V[2,3]]");
+    assert!(root.selected(c, 3).is_none());
+
+    let windows: Vec<_> = root.windows().iter().map(|w| w.id).collect();
+    assert_eq!(windows, (1..=3u32).map(WindowId::from).collect::<Vec<_>>());
+}
+
+#[test]
+fn move_selected_split_swaps_it_as_a_whole() {
+    let mut root = tree_with_nested_vertical();
+
+    let moved = root
+        .move_container(WindowId::from(2u32), 1, Direction::Left, 0.0)
+        .unwrap();
+
+    assert_eq!(moved, MoveResult::Moved);
+    assert_eq!(shape(&root), "H[V[2,3],1]");
+}
+
+#[test]
+fn move_selection_holding_every_window_does_nothing() {
+    let mut root = tree_with_nested_vertical();
+
+    let moved = root
+        .move_container(WindowId::from(2u32), 2, Direction::Up, 0.0)
+        .unwrap();
+
+    assert_eq!(moved, MoveResult::AtEdge);
+    assert_eq!(shape(&root), "H[1,V[2,3]]");
+}
+
+#[test]
+fn take_selected_split_removes_it_from_the_tree() {
+    let mut root = tree_with_nested_vertical();
+    let Container::Split(split) = &mut root else {
+        unreachable!()
+    };
+
+    let taken = split.take_container(WindowId::from(3u32), 1, 0.0).unwrap();
+
+    assert_eq!(shape(&taken), "V[2,3]");
+    assert_eq!(shape(&root), "H[1]");
+    assert!(approx(
+        root.window_bounds_by_id()[&WindowId::from(1u32)].width,
+        900.0
+    ));
+}
+
+#[test]
+fn resize_selected_split_moves_its_divider_with_its_sibling() {
+    let mut root = tree_with_nested_vertical();
+    let (a, b, c) = (
+        WindowId::from(1u32),
+        WindowId::from(2u32),
+        WindowId::from(3u32),
+    );
+
+    root.resize_container(b, 1, Direction::Left, 50.0, 200.0, 0.0)
+        .unwrap();
+
+    // The vertical split grows to the left as a whole, so both of its windows
+    // widen together.
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&a].width, 400.0));
+    assert!(approx(bounds[&b].width, 500.0));
+    assert!(approx(bounds[&c].width, 500.0));
+    assert!(approx(bounds[&b].height, bounds[&c].height));
+}
+
+#[test]
+fn resize_selection_across_its_split_errors() {
+    let mut root = tree_with_nested_vertical();
+
+    assert!(
+        root.resize_container(WindowId::from(2u32), 1, Direction::Up, 50.0, 200.0, 0.0)
+            .is_err()
+    );
+}
+
+fn tree_with_nested_horizontal() -> Container {
+    let mut root = tree_with_nested_vertical();
+    let c = WindowId::from(3u32);
+
+    root.split_window(c, Horizontal).unwrap();
+    root.add_window_beside(c, dummy_window(WindowId::from(4u32)), 0.0)
+        .unwrap();
+
+    assert_eq!(shape(&root), "H[1,V[2,H[3,4]]]");
+    root
+}
+
+#[test]
+fn resize_walks_up_to_nearest_split_along_direction() {
+    let mut root = tree_with_nested_vertical();
+
+    root.resize_container(WindowId::from(3u32), 0, Direction::Left, 50.0, 200.0, 0.0)
+        .unwrap();
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&WindowId::from(1u32)].width, 400.0));
+    assert!(approx(bounds[&WindowId::from(3u32)].width, 500.0));
+    assert!(approx(bounds[&WindowId::from(3u32)].height, 300.0));
+}
+
+#[test]
+fn resize_skips_ancestors_without_neighbour_on_that_side() {
+    let mut root = tree_with_nested_horizontal();
+    let c = WindowId::from(3u32);
+
+    // Window 3 is first in its horizontal split, so growing left moves the
+    // root's divider instead.
+    root.resize_container(c, 0, Direction::Left, 50.0, 200.0, 0.0)
+        .unwrap();
+    // Growing up passes over both horizontal splits to the vertical one.
+    root.resize_container(c, 0, Direction::Up, 50.0, 200.0, 0.0)
+        .unwrap();
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&WindowId::from(1u32)].width, 400.0));
+    assert!(approx(bounds[&WindowId::from(2u32)].height, 250.0));
+    assert!(approx(bounds[&c].height, 350.0));
+    assert!(approx(bounds[&WindowId::from(4u32)].height, 350.0));
+}
+
+#[test]
+fn resize_towards_screen_edge_errors_at_any_depth() {
+    let mut root = tree_with_nested_horizontal();
+
+    for (id, direction) in [
+        (1u32, Direction::Left),
+        (4, Direction::Right),
+        (2, Direction::Up),
+        (4, Direction::Down),
+    ] {
+        assert!(
+            root.resize_container(WindowId::from(id), 0, direction, 50.0, 200.0, 0.0)
+                .is_err()
+        );
     }
+}
+
+#[test]
+fn wrapping_a_split_nests_it_whole() {
+    let mut root = tree_with_nested_vertical();
+
+    root.selected_mut(WindowId::from(2u32), 1)
+        .unwrap()
+        .wrap(Horizontal);
+
+    assert_eq!(shape(&root), "H[1,H[V[2,3]]]");
+}
+
+#[test]
+fn toggle_split_flips_axis_and_keeps_weights() {
+    let (mut root, a, b) = two_window_split();
+    let Container::Split(split) = &mut root else {
+        unreachable!()
+    };
+    split.weights = vec![2.0, 1.0];
+
+    let layout = split.change_layout(LayoutChange::ToggleSplit, 0.0).unwrap();
+
+    assert_eq!(layout, Layout::Tiled);
+    assert_eq!(split.axis, Vertical);
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&a].height, 400.0));
+    assert!(approx(bounds[&b].height, 200.0));
+    assert!(approx(bounds[&b].y, 400.0));
+    assert!(approx(bounds[&a].width, 900.0));
+}
+
+#[test]
+fn toggle_split_relays_out_nested_splits() {
+    let mut root = tree_with_nested_vertical();
+    let Container::Split(split) = &mut root else {
+        unreachable!()
+    };
+
+    split.change_layout(LayoutChange::ToggleSplit, 0.0).unwrap();
+
+    assert_eq!(shape(&root), "V[1,V[2,3]]");
+    let bounds = root.window_bounds_by_id();
+    let (b, c) = (bounds[&WindowId::from(2u32)], bounds[&WindowId::from(3u32)]);
+    assert!(approx(b.width, 900.0) && approx(c.width, 900.0));
+    assert!(approx(b.y, 300.0));
+    assert!(approx(c.y, 450.0));
+    assert!(approx(c.height, 150.0));
+}
+
+#[test]
+fn toggle_split_returns_tabbed_split_to_tiling_on_same_axis() {
+    let (mut root, _, _) = two_window_split();
+    let Container::Split(split) = &mut root else {
+        unreachable!()
+    };
+    split.set_layout(Layout::Tabbed, 0.0).unwrap();
+
+    split.change_layout(LayoutChange::ToggleSplit, 0.0).unwrap();
+
+    assert_eq!(split.layout, Layout::Tiled);
+    assert_eq!(split.axis, Horizontal);
+}
+
+#[test]
+fn split_window_alone_in_split_changes_axis() {
+    let mut root = tree_of(Horizontal, &[WindowId::from(1u32)]);
+
+    root.split_window(WindowId::from(1u32), Vertical).unwrap();
+
+    assert_eq!(shape(&root), "V[1]");
+}
+
+#[test]
+fn split_window_wraps_leaf_in_place() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids);
+    let before = root.window_bounds_by_id();
+
+    root.split_window(ids[1], Vertical).unwrap();
 
-    /// After a nested split's only window is removed, the sibling of that nested
-    /// split should expand to fill the whole parent — not keep its old half-width.
-    ///
-    /// Scenario
-    /// --------
-    ///   root (H-split, 900 wide)
-    ///   ├── leaf A  (450 wide)          ← stays
-    ///   └── inner (H-split, 450 wide)
-    ///       └── leaf B  (450 wide)     ← removed; inner collapses to Empty
-    ///
-    /// After removal of B:
-    ///   root should collapse to a single-child split (or Empty→leaf)
-    ///   and A's bounds should span the full 900 width.
-    #[test]
-    fn remove_nested_window_rebalances_parent() {
-        let (mut root, a, b) = two_window_split();
-
-        // Split leaf B into its own inner split (simulates user pressing split).
-        // parent_leaf_of_window_mut returns the Leaf itself; calling split() on it
-        // converts it to a Split containing that leaf.
-        root.parent_leaf_of_window_mut(b)
+    assert_eq!(shape(&root), "H[1,V[2],3]");
+    assert_eq!(root.window_bounds_by_id(), before);
+}
+
+#[test]
+fn split_windows_build_a_grid() {
+    let ids: Vec<WindowId> = (1..=4u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids[..2]);
+
+    for (focused, new) in [(ids[0], ids[2]), (ids[1], ids[3])] {
+        root.split_window(focused, Vertical).unwrap();
+        root.get_parent_of_window_mut(focused)
             .unwrap()
-            .split(Axis::Horizontal)
+            .add_window(dummy_window(new), 0.0)
             .unwrap();
+    }
 
-        // Sanity: both windows still present.
-        assert!(root.contains_window(a));
-        assert!(root.contains_window(b));
+    assert_eq!(shape(&root), "H[V[1,3],V[2,4]]");
+    let bounds = root.window_bounds_by_id();
+    for (id, x, y) in [
+        (1u32, 0.0, 0.0),
+        (2, 450.0, 0.0),
+        (3, 0.0, 300.0),
+        (4, 450.0, 300.0),
+    ] {
+        let b = bounds[&WindowId::from(id)];
+        assert!(approx(b.x, x) && approx(b.y, y), "window {id} at {b:?}");
+        assert!(approx(b.width, 450.0) && approx(b.height, 300.0));
+    }
+}
 
-        // Remove B — it now lives inside the inner split.
-        let removed = root.remove_window(b, 0.0).unwrap();
-        assert_eq!(removed, Some(b), "B must be reported as removed");
-        assert!(!root.contains_window(b));
+#[test]
+fn removing_window_collapses_single_child_split() {
+    let mut root = tree_with_nested_vertical();
 
-        // A must still exist.
-        assert!(root.contains_window(a));
+    root.remove_window(WindowId::from(3u32), 0.0).unwrap();
 
-        // A's bounds must now span the full 900 px, not stay at the old 450.
-        let bounds_map = root.window_bounds_by_id();
-        let a_bounds = bounds_map[&a];
-        assert!(
-            approx(a_bounds.width, 900.0),
-            "after nested removal, surviving window must expand to full width; got {}",
-            a_bounds.width
-        );
+    assert_eq!(shape(&root), "H[1,2]");
+    assert!(root.is_normalised());
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&WindowId::from(2u32)].height, 600.0));
+}
+
+#[test]
+fn removing_window_lets_root_take_on_its_only_split() {
+    let mut root = tree_with_nested_vertical();
+
+    root.remove_window(WindowId::from(1u32), 0.0).unwrap();
+
+    assert_eq!(shape(&root), "V[2,3]");
+    assert!(root.is_normalised());
+    assert_eq!(root.bounds(), wide_bounds());
+}
+
+#[test]
+fn removing_window_flattens_same_axis_nesting_and_keeps_shares() {
+    // Build `H[1,V[2,H[3,4]]]` with weights on the inner horizontal split.
+    let ids: Vec<WindowId> = (1..=4u32).map(WindowId::from).collect();
+    let mut root = tree_with_nested_vertical();
+    root.split_window(ids[2], Horizontal).unwrap();
+    root.get_parent_of_window_mut(ids[2])
+        .unwrap()
+        .add_window(dummy_window(ids[3]), 0.0)
+        .unwrap();
+    let Some(Container::Split(inner)) = root.get_parent_of_window_mut(ids[2]) else {
+        unreachable!()
+    };
+    inner.weights = vec![1.0, 2.0];
+    assert_eq!(shape(&root), "H[1,V[2,H[3,4]]]");
+
+    // Removing 2 leaves the vertical split with one child, which collapses
+    // and brings the inner horizontal split up beside 1.
+    root.remove_window(ids[1], 0.0).unwrap();
+
+    assert_eq!(shape(&root), "H[1,3,4]");
+    assert!(root.is_normalised());
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&ids[0]].width, 450.0));
+    assert!(approx(bounds[&ids[2]].width, 150.0));
+    assert!(approx(bounds[&ids[3]].width, 300.0));
+}
+
+#[test]
+fn split_window_is_left_unnormalised_until_the_next_mutation() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids);
+
+    root.split_window(ids[1], Vertical).unwrap();
+    assert!(!root.is_normalised());
+
+    root.remove_window(ids[2], 0.0).unwrap();
+    assert_eq!(shape(&root), "H[1,2]");
+    assert!(root.is_normalised());
+}
+
+#[test]
+fn tabbed_split_is_not_flattened_into_tiled_parent() {
+    let mut root = tree_with_nested_vertical();
+    let Some(Container::Split(inner)) = root.get_parent_of_window_mut(WindowId::from(2u32)) else {
+        unreachable!()
+    };
+    inner.set_layout(Layout::Tabbed, 0.0).unwrap();
+    inner.axis = Horizontal;
+
+    root.normalise(0.0).unwrap();
+
+    assert_eq!(shape(&root), "H[1,H[2,3]]");
+    assert!(root.is_normalised());
+}
+
+fn window_with_min(id: u32, min_width: f64, min_height: f64) -> Window {
+    Window {
+        id: WindowId::from(id),
+        min_width,
+        min_height,
     }
+}
 
-    /// Simpler case: direct removal from a two-child split still works after the
-    /// refactor (regression guard for the direct path).
-    #[test]
-    fn remove_direct_child_rebalances_sibling() {
-        let (mut root, a, b) = two_window_split();
+#[test]
+fn solve_lengths_shares_by_weight_when_minimums_fit() {
+    let lengths = solve_lengths(900.0, &[1.0, 2.0], &[100.0, 100.0]);
 
-        root.remove_window(a, 0.0).unwrap();
+    assert!(approx(lengths[0], 300.0));
+    assert!(approx(lengths[1], 600.0));
+}
 
-        assert!(!root.contains_window(a));
-        assert!(root.contains_window(b));
+#[test]
+fn solve_lengths_holds_short_children_at_their_minimum() {
+    let lengths = solve_lengths(900.0, &[1.0, 1.0, 1.0], &[500.0, 0.0, 0.0]);
 
-        let bounds_map = root.window_bounds_by_id();
-        let b_bounds = bounds_map[&b];
-        assert!(
-            approx(b_bounds.width, 900.0),
-            "surviving window must fill full width after direct removal; got {}",
-            b_bounds.width
-        );
+    assert!(approx(lengths[0], 500.0));
+    assert!(approx(lengths[1], 200.0));
+    assert!(approx(lengths[2], 200.0));
+}
+
+#[test]
+fn solve_lengths_repeats_until_no_child_falls_short() {
+    // Holding the first child at 400 leaves 250 each for the others, which is
+    // too little for the second.
+    let lengths = solve_lengths(900.0, &[1.0, 1.0, 1.0], &[400.0, 300.0, 0.0]);
+
+    assert!(approx(lengths[0], 400.0));
+    assert!(approx(lengths[1], 300.0));
+    assert!(approx(lengths[2], 200.0));
+}
+
+#[test]
+fn solve_lengths_shares_by_weight_when_minimums_overflow() {
+    let lengths = solve_lengths(900.0, &[1.0, 2.0], &[600.0, 600.0]);
+
+    assert!(approx(lengths[0], 300.0));
+    assert!(approx(lengths[1], 600.0));
+}
+
+#[test]
+fn add_window_gives_minimum_before_sharing_leftover() {
+    let mut root = tree_of(Horizontal, &[]);
+    root.add_window(window_with_min(1, 600.0, 0.0), 0.0)
+        .unwrap();
+
+    // An equal split would give each window 450, below the first's minimum.
+    root.add_window(window_with_min(2, 0.0, 0.0), 0.0).unwrap();
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&WindowId::from(1u32)].width, 600.0));
+    assert!(approx(bounds[&WindowId::from(2u32)].width, 300.0));
+}
+
+#[test]
+fn add_window_errors_only_when_minimums_cannot_fit() {
+    let mut root = tree_of(Horizontal, &[]);
+    root.add_window(window_with_min(1, 600.0, 0.0), 0.0)
+        .unwrap();
+    root.add_window(window_with_min(2, 300.0, 0.0), 0.0)
+        .unwrap();
+
+    assert!(matches!(
+        root.add_window(window_with_min(3, 1.0, 0.0), 0.0),
+        Err(Error::CannotFitWindow)
+    ));
+    assert_eq!(shape(&root), "H[1,2]");
+
+    assert!(matches!(
+        root.add_window(window_with_min(3, 0.0, 601.0), 0.0),
+        Err(Error::CannotFitWindow)
+    ));
+}
+
+#[test]
+fn split_minimums_add_up_along_axis() {
+    let padding = 5.0;
+    let mut split = Split::new(wide_bounds(), Horizontal, padding, vec![]);
+    split
+        .add_window(window_with_min(1, 100.0, 50.0), padding)
+        .unwrap();
+    split
+        .add_window(window_with_min(2, 200.0, 80.0), padding)
+        .unwrap();
+
+    assert!(approx(split.min_width(), 100.0 + 200.0 + 3.0 * padding));
+    assert!(approx(split.min_height(), 80.0 + 2.0 * padding));
+
+    split.set_layout(Layout::Stacked, padding).unwrap();
+    assert!(approx(split.min_width(), 200.0 + 2.0 * padding));
+    assert!(approx(
+        split.min_height(),
+        80.0 + 2.0 * TITLE_STRIP_HEIGHT + 2.0 * padding
+    ));
+}
+
+#[test]
+fn nested_minimums_are_respected_along_each_axis() {
+    // `H[1,V[2,3]]`, where the vertical split must be at least as wide as its
+    // widest window, so window 1 gives up space for window 3 to fit.
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids[..1]);
+    root.add_window(window_with_min(2, 500.0, 400.0), 0.0)
+        .unwrap();
+    root.split_window(ids[1], Vertical).unwrap();
+    root.add_window_beside(ids[1], window_with_min(3, 600.0, 100.0), 0.0)
+        .unwrap();
+
+    assert_eq!(shape(&root), "H[1,V[2,3]]");
+    assert!(approx(root.min_width(), 600.0));
+    assert!(approx(root.min_height(), 500.0));
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&ids[0]].width, 300.0));
+    assert!(approx(bounds[&ids[1]].width, 600.0));
+    assert!(approx(bounds[&ids[1]].height, 400.0));
+    assert!(approx(bounds[&ids[2]].height, 200.0));
+}
+
+#[test]
+fn add_window_beside_errors_when_tree_minimums_cannot_fit() {
+    let ids: Vec<WindowId> = (1..=2u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &[]);
+    root.add_window(window_with_min(1, 400.0, 0.0), 0.0)
+        .unwrap();
+    root.add_window(window_with_min(2, 0.0, 0.0), 0.0).unwrap();
+    root.split_window(ids[1], Vertical).unwrap();
+
+    assert!(matches!(
+        root.add_window_beside(ids[1], window_with_min(3, 501.0, 0.0), 0.0),
+        Err(Error::CannotFitWindow)
+    ));
+    assert_eq!(shape(&root), "H[1,V[2]]");
+}
+
+#[test]
+fn balance_resets_nested_splits_to_equal_shares() {
+    let padding = 5.0;
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut split = Split::new(wide_bounds(), Horizontal, padding, vec![]);
+    for id in 1..=2 {
+        split
+            .add_window(window_with_min(id, 0.0, 0.0), padding)
+            .unwrap();
     }
+    let mut root = Container::Split(split);
+    root.split_window(ids[1], Vertical).unwrap();
+    root.add_window_beside(ids[1], window_with_min(3, 0.0, 0.0), padding)
+        .unwrap();
+    root.resize_container(ids[0], 0, Direction::Right, 50.0, 200.0, padding)
+        .unwrap();
+    root.resize_container(ids[1], 0, Direction::Down, 50.0, 200.0, padding)
+        .unwrap();
 
-    /// Three-window split: removing the middle window rebalances the outer two to
-    /// each take half the space (no ghost third slot).
-    #[test]
-    fn remove_middle_of_three_no_ghost_slot() {
-        let mut root = Container::Empty {
-            bounds: Bounds {
-                x: 0.0,
-                y: 0.0,
-                width: 900.0,
-                height: 600.0,
-            },
-        };
-        let a = WindowId::from(1u32);
-        let b = WindowId::from(2u32);
-        let c = WindowId::from(3u32);
-        for id in [a, b, c] {
-            root.add_window(
-                Window {
-                    id,
-                    min_width: 0.0,
-                    min_height: 0.0,
-                },
-                0.0,
-            )
+    let Container::Split(split) = &mut root else {
+        unreachable!()
+    };
+    split.balance(padding).unwrap();
+
+    let outer = spread_bounds_along_axis(wide_bounds(), Horizontal, 2, padding);
+    let inner = spread_bounds_along_axis(split.children[1].bounds(), Vertical, 2, padding);
+    let bounds = root.window_bounds_by_id();
+    assert_eq!(bounds[&ids[0]], outer[0]);
+    assert_eq!(bounds[&ids[1]], inner[0]);
+    assert_eq!(bounds[&ids[2]], inner[1]);
+}
+
+fn resize(root: &mut Container, id: u32, resize: Resize) -> Result<()> {
+    root.apply_resize(WindowId::from(id), 0, resize, 50.0, 100.0, 0.0)
+}
+
+#[test]
+fn grow_width_takes_space_from_next_sibling() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids);
+
+    resize(
+        &mut root,
+        1,
+        Resize::Grow(Horizontal, Some(Length::Px(30.0))),
+    )
+    .unwrap();
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&ids[0]].width, 330.0));
+    assert!(approx(bounds[&ids[1]].width, 270.0));
+    assert!(approx(bounds[&ids[2]].width, 300.0));
+}
+
+#[test]
+fn grow_last_child_takes_space_from_previous_sibling() {
+    let (mut root, a, b) = two_window_split();
+
+    resize(&mut root, 2, Resize::Grow(Horizontal, None)).unwrap();
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&a].width, 400.0));
+    assert!(approx(bounds[&b].width, 500.0));
+    assert!(approx(bounds[&b].x, 400.0));
+}
+
+#[test]
+fn shrink_by_percentage_points_of_parent_split() {
+    // `H[1,V[2,3]]`: shrinking 2's height by 10 ppt is resolved against the
+    // vertical split's 600 pixel height.
+    let mut root = tree_with_nested_vertical();
+
+    resize(
+        &mut root,
+        2,
+        Resize::Shrink(Vertical, Some(Length::Ppt(10.0))),
+    )
+    .unwrap();
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&WindowId::from(2u32)].height, 240.0));
+    assert!(approx(bounds[&WindowId::from(3u32)].height, 360.0));
+}
+
+#[test]
+fn resize_never_shrinks_below_minimum() {
+    let (mut root, a, b) = two_window_split();
+
+    resize(
+        &mut root,
+        1,
+        Resize::Shrink(Horizontal, Some(Length::Px(1000.0))),
+    )
+    .unwrap();
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&a].width, 100.0));
+    assert!(approx(bounds[&b].width, 800.0));
+}
+
+#[test]
+fn grow_width_in_vertical_split_resizes_horizontal_ancestor() {
+    let mut root = tree_with_nested_vertical();
+
+    resize(
+        &mut root,
+        2,
+        Resize::Grow(Horizontal, Some(Length::Px(50.0))),
+    )
+    .unwrap();
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&WindowId::from(1u32)].width, 400.0));
+    assert!(approx(bounds[&WindowId::from(2u32)].width, 500.0));
+    assert!(approx(bounds[&WindowId::from(3u32)].width, 500.0));
+}
+
+#[test]
+fn resize_set_sets_length_within_nearest_split_along_each_axis() {
+    let mut root = tree_with_nested_vertical();
+
+    // Window 2's own split is vertical, so its width is set within the root.
+    resize(
+        &mut root,
+        2,
+        Resize::Set {
+            width: Some(Length::Ppt(25.0)),
+            height: Some(Length::Px(200.0)),
+        },
+    )
+    .unwrap();
+
+    let bounds = root.window_bounds_by_id();
+    let (a, b, c) = (
+        bounds[&WindowId::from(1u32)],
+        bounds[&WindowId::from(2u32)],
+        bounds[&WindowId::from(3u32)],
+    );
+    assert!(approx(a.width, 675.0));
+    assert!(approx(b.width, 225.0) && approx(c.width, 225.0));
+    assert!(approx(b.height, 200.0));
+    assert!(approx(c.height, 400.0));
+}
+
+#[test]
+fn resize_survives_later_relayout() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids[..2]);
+
+    resize(
+        &mut root,
+        1,
+        Resize::Grow(Horizontal, Some(Length::Px(150.0))),
+    )
+    .unwrap();
+    root.add_window_beside(ids[0], dummy_window(ids[2]), 0.0)
+        .unwrap();
+    root.remove_window(ids[2], 0.0).unwrap();
+
+    let bounds = root.window_bounds_by_id();
+    assert!(approx(bounds[&ids[0]].width, 600.0));
+    assert!(approx(bounds[&ids[1]].width, 300.0));
+}
+
+/// A 2x2 grid, `H[V[1,3],V[2,4]]`, laid out with `padding`.
+fn grid(padding: f64) -> Container {
+    let ids: Vec<WindowId> = (1..=4u32).map(WindowId::from).collect();
+    let mut root = Container::Split(Split::new(wide_bounds(), Horizontal, padding, vec![]));
+    for &id in &ids[..2] {
+        root.add_window(dummy_window(id), padding).unwrap();
+    }
+
+    for (focused, new) in [(ids[0], ids[2]), (ids[1], ids[3])] {
+        root.split_window(focused, Vertical).unwrap();
+        root.add_window_beside(focused, dummy_window(new), padding)
             .unwrap();
+    }
+
+    assert_eq!(shape(&root), "H[V[1,3],V[2,4]]");
+    root
+}
+
+fn window_in_direction(root: &Container, id: u32, direction: Direction) -> Option<WindowId> {
+    root.window_in_direction(WindowId::from(id), direction)
+}
+
+#[test]
+fn focus_in_grid_stays_on_same_row_or_column() {
+    for &padding in PADDING_VALUES {
+        let root = grid(padding);
+
+        for (from, direction, to) in [
+            (1u32, Direction::Right, Some(2u32)),
+            (1, Direction::Down, Some(3)),
+            (4, Direction::Left, Some(3)),
+            (4, Direction::Up, Some(2)),
+            (3, Direction::Right, Some(4)),
+            (1, Direction::Left, None),
+            (1, Direction::Up, None),
+            (4, Direction::Right, None),
+            (4, Direction::Down, None),
+        ] {
+            assert_eq!(
+                window_in_direction(&root, from, direction),
+                to.map(WindowId::from),
+                "{from} {direction} with padding {padding}"
+            );
         }
+    }
+}
+
+#[test]
+fn focus_crosses_nested_splits_to_nearest_overlapping_window() {
+    // Window 1 spans the full height, so both 2 and 3 overlap it; 2 and 4 are
+    // stacked on the right of a nested split.
+    let root = tree_with_nested_horizontal();
+
+    assert_eq!(
+        window_in_direction(&root, 1, Direction::Right),
+        Some(WindowId::from(2u32))
+    );
+    assert_eq!(
+        window_in_direction(&root, 4, Direction::Left),
+        Some(WindowId::from(3u32))
+    );
+    assert_eq!(
+        window_in_direction(&root, 3, Direction::Left),
+        Some(WindowId::from(1u32))
+    );
+    assert_eq!(
+        window_in_direction(&root, 4, Direction::Up),
+        Some(WindowId::from(2u32))
+    );
+    assert_eq!(
+        window_in_direction(&root, 2, Direction::Down),
+        Some(WindowId::from(3u32))
+    );
+}
+
+#[test]
+fn focus_skips_tabs_hidden_behind_the_visible_one() {
+    let mut root = grid(0.0);
+    root.get_parent_of_window_mut(WindowId::from(2u32))
+        .map(|c| match c {
+            Container::Split(split) => split.change_layout(LayoutChange::Set(Layout::Tabbed), 0.0),
+            Container::Leaf(_) => unreachable!(),
+        })
+        .unwrap()
+        .unwrap();
+
+    // Window 2's tab is shown only while it is focused, so from window 1 the
+    // tabbed split's first child is the one on screen.
+    assert_eq!(
+        window_in_direction(&root, 1, Direction::Right),
+        Some(WindowId::from(2u32))
+    );
+    assert_eq!(
+        window_in_direction(&root, 3, Direction::Right),
+        Some(WindowId::from(2u32))
+    );
+}
+
+#[test]
+fn window_at_edge_is_furthest_overlapping_window() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let root = tree_of(Horizontal, &ids);
+
+    assert_eq!(root.window_at_edge(ids[2], Direction::Left), Some(ids[0]));
+    assert_eq!(root.window_at_edge(ids[0], Direction::Left), None);
+}
+
+fn screen(x: f64, y: f64, width: f64, height: f64) -> Bounds {
+    Bounds {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn nearest_display_must_overlap_on_perpendicular_axis() {
+    let main = screen(0.0, 0.0, 1440.0, 900.0);
+    let displays = [
+        ("right", screen(1440.0, 200.0, 1920.0, 1080.0)),
+        ("above", screen(200.0, -1080.0, 1920.0, 1080.0)),
+        ("far right", screen(3360.0, 0.0, 1440.0, 900.0)),
+        ("below right", screen(1440.0, 900.0, 800.0, 600.0)),
+    ];
+
+    assert_eq!(
+        nearest_in_direction(main, displays, Direction::Right),
+        Some("right")
+    );
+    assert_eq!(
+        nearest_in_direction(main, displays, Direction::Up),
+        Some("above")
+    );
+    assert_eq!(nearest_in_direction(main, displays, Direction::Down), None);
+    assert_eq!(nearest_in_direction(main, displays, Direction::Left), None);
+}
+
+#[test]
+fn furthest_display_wraps_around_from_the_opposite_edge() {
+    let right = screen(2880.0, 0.0, 1440.0, 900.0);
+    let displays = [
+        ("left", screen(0.0, 0.0, 1440.0, 900.0)),
+        ("middle", screen(1440.0, 0.0, 1440.0, 900.0)),
+    ];
+
+    assert_eq!(
+        nearest_in_direction(right, displays, Direction::Right),
+        None
+    );
+    assert_eq!(
+        furthest_in_direction(right, displays, Direction::Left),
+        Some("left")
+    );
+}
+
+fn record_focus(root: &mut Container, ids: &[u32]) {
+    for &id in ids {
+        root.record_focus(WindowId::from(id));
+    }
+}
+
+#[test]
+fn closing_window_focuses_previously_focused_sibling() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids);
+    record_focus(&mut root, &[1, 3, 2]);
+
+    assert_eq!(root.focus_successor(ids[1]), Some(ids[2]));
+}
+
+#[test]
+fn closing_never_focused_neighbours_prefers_previous_sibling() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let root = tree_of(Horizontal, &ids);
+
+    assert_eq!(root.focus_successor(ids[1]), Some(ids[0]));
+    assert_eq!(root.focus_successor(ids[0]), Some(ids[1]));
+}
+
+#[test]
+fn closing_window_enters_sibling_split_at_its_last_focused_child() {
+    let mut root = tree_with_nested_vertical();
+    record_focus(&mut root, &[3, 2, 1]);
 
-        root.remove_window(b, 0.0).unwrap();
+    // Within the vertical split 2 was focused after 3, so it takes focus.
+    assert_eq!(
+        root.focus_successor(WindowId::from(1u32)),
+        Some(WindowId::from(2u32))
+    );
+}
+
+#[test]
+fn closing_only_child_of_split_walks_up_for_a_sibling() {
+    let mut root = tree_with_nested_vertical();
+    let a = WindowId::from(1u32);
+    root.split_window(a, Vertical).unwrap();
+    record_focus(&mut root, &[3, 1]);
+
+    assert_eq!(shape(&root), "H[V[1],V[2,3]]");
+    assert_eq!(root.focus_successor(a), Some(WindowId::from(3u32)));
+}
+
+#[test]
+fn removing_window_drops_it_from_focus_stacks() {
+    let mut root = tree_with_nested_vertical();
+    record_focus(&mut root, &[3, 1]);
+
+    root.remove_window(WindowId::from(3u32), 0.0).unwrap();
+
+    let Container::Split(split) = &root else {
+        unreachable!()
+    };
+    assert_eq!(split.focus_stack, vec![WindowId::from(1u32)]);
+}
 
-        let bounds_map = root.window_bounds_by_id();
-        assert!(approx(bounds_map[&a].width, 450.0));
-        assert!(approx(bounds_map[&c].width, 450.0));
-        // The two survivors must not overlap and must tile perfectly.
-        assert!(approx(bounds_map[&a].x, 0.0));
-        assert!(approx(bounds_map[&c].x, 450.0));
+#[test]
+fn focus_enters_split_at_its_last_focused_child() {
+    let mut root = tree_with_nested_vertical();
+    record_focus(&mut root, &[3, 1]);
+
+    assert_eq!(
+        window_in_direction(&root, 1, Direction::Right),
+        Some(WindowId::from(3u32))
+    );
+}
+
+#[test]
+fn focus_enters_split_only_at_windows_in_direction() {
+    let mut root = grid(0.0);
+    record_focus(&mut root, &[4, 1]);
+
+    // Window 4 was focused last on the right, but sits below window 1.
+    assert_eq!(
+        window_in_direction(&root, 1, Direction::Right),
+        Some(WindowId::from(2u32))
+    );
+}
+
+#[test]
+fn tabbed_split_shows_and_enters_its_last_focused_tab() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids);
+    root.split_window(ids[2], Vertical).unwrap();
+    root.add_window_beside(ids[2], dummy_window(WindowId::from(4u32)), 0.0)
+        .unwrap();
+    root.get_parent_of_window_mut(ids[2])
+        .map(|c| match c {
+            Container::Split(split) => split.change_layout(LayoutChange::Set(Layout::Tabbed), 0.0),
+            Container::Leaf(_) => unreachable!(),
+        })
+        .unwrap()
+        .unwrap();
+    record_focus(&mut root, &[4, 2]);
+
+    let visible = root.visible_window_bounds(ids[1]);
+    assert!(visible.contains_key(&WindowId::from(4u32)));
+    assert!(!visible.contains_key(&ids[2]));
+    assert_eq!(
+        window_in_direction(&root, 2, Direction::Right),
+        Some(WindowId::from(4u32))
+    );
+}
+
+#[test]
+fn restore_window_returns_window_to_its_place_and_share() {
+    let ids: Vec<WindowId> = (1..=3u32).map(WindowId::from).collect();
+    let mut root = tree_of(Horizontal, &ids);
+    root.resize_container(ids[1], 0, Direction::Right, 150.0, 0.0, 0.0)
+        .unwrap();
+    let before = root.window_bounds_by_id();
+
+    for &id in &ids[..2] {
+        let position = root.position_of_window(id).unwrap();
+        root.remove_window(id, 0.0).unwrap();
+        root.restore_window(&position, 0.0).unwrap();
+
+        assert_eq!(shape(&root), "H[1,2,3]");
+        let after = root.window_bounds_by_id();
+        for id in &ids {
+            assert!(approx(after[id].x, before[id].x));
+            assert!(approx(after[id].width, before[id].width));
+        }
     }
 }
+
+#[test]
+fn restore_window_rebuilds_a_collapsed_parent() {
+    let mut root = tree_with_nested_vertical();
+    let c = WindowId::from(3u32);
+
+    let position = root.position_of_window(c).unwrap();
+    root.remove_window(c, 0.0).unwrap();
+    assert_eq!(shape(&root), "H[1,2]");
+
+    root.restore_window(&position, 0.0).unwrap();
+    assert_eq!(shape(&root), "H[1,V[2,3]]");
+    assert!(root.is_normalised());
+}
+
+#[test]
+fn restore_window_errors_once_its_siblings_have_gone() {
+    let mut root = tree_with_nested_vertical();
+    let (b, c) = (WindowId::from(2u32), WindowId::from(3u32));
+
+    let position = root.position_of_window(c).unwrap();
+    root.remove_window(c, 0.0).unwrap();
+    root.remove_window(b, 0.0).unwrap();
+    root.add_window(dummy_window(WindowId::from(4u32)), 0.0)
+        .unwrap();
+
+    assert!(matches!(
+        root.restore_window(&position, 0.0),
+        Err(Error::CannotFindWindow)
+    ));
+    assert_eq!(shape(&root), "H[1,4]");
+}
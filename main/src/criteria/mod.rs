@@ -0,0 +1,273 @@
+#[cfg(test)]
+mod tests;
+
+use core_graphics::WindowId;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A selection of windows in the style of i3's criteria, for example
+/// `[app_name="Safari" title="^Inbox" floating]`. Every criterion given must
+/// match, and criteria giving none match every window. Text criteria are
+/// regular expressions, matching anywhere in the text unless anchored.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Criteria {
+    /// The name of the application that owns the window, in place of i3's
+    /// `class`.
+    pub app_name: Option<Regex>,
+    pub title: Option<Regex>,
+    /// The window's accessibility role, such as `AXWindow`.
+    pub role: Option<Regex>,
+    /// The window's accessibility subrole, such as `AXDialog`.
+    pub subrole: Option<Regex>,
+    /// Matches a window with any mark matching.
+    pub con_mark: Option<Regex>,
+    pub id: Option<u32>,
+    pub pid: Option<i32>,
+    /// The window's Core Graphics layer; application windows are on layer 0.
+    pub layer: Option<i32>,
+    /// Whether the window must be floating, given as `floating`, or tiled,
+    /// given as `tiling`.
+    pub floating: Option<bool>,
+}
+
+/// A regular expression in a criterion, which like PCRE matches anywhere
+/// within the text unless anchored. It is kept as its source when serialised.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Regex(regex::Regex);
+
+impl Regex {
+    pub fn new(source: &str) -> Result<Self, String> {
+        regex::Regex::new(source)
+            .map(Self)
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Whether the regex matches anywhere within `text`.
+    pub fn is_match(&self, text: &str) -> bool {
+        self.0.is_match(text)
+    }
+}
+
+impl PartialEq for Regex {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Display for Regex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl TryFrom<String> for Regex {
+    type Error = String;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Self::new(&source)
+    }
+}
+
+impl From<Regex> for String {
+    fn from(regex: Regex) -> Self {
+        regex.as_str().to_string()
+    }
+}
+
+/// The properties of a window that criteria are matched against, taken from
+/// the window manager's window and its Core Graphics window.
+#[derive(Debug, Clone)]
+pub struct WindowProperties<'a> {
+    pub id: WindowId,
+    pub app_name: Option<&'a str>,
+    pub title: Option<&'a str>,
+    pub pid: i32,
+    pub layer: i32,
+    pub role: Option<&'a str>,
+    pub subrole: Option<&'a str>,
+    pub floating: bool,
+    pub marks: Vec<&'a str>,
+}
+
+impl<'a> WindowProperties<'a> {
+    /// The properties of a window that is not yet tracked, so is tiled and
+    /// unmarked.
+    pub fn of(window: &'a core_graphics::Window) -> Self {
+        Self {
+            id: window.number(),
+            app_name: window.owner_name(),
+            title: window.name(),
+            pid: window.owner_pid(),
+            layer: window.layer(),
+            role: None,
+            subrole: None,
+            floating: false,
+            marks: Vec::new(),
+        }
+    }
+}
+
+impl Criteria {
+    pub fn matches(&self, window: &WindowProperties) -> bool {
+        fn text(regex: &Option<Regex>, text: Option<&str>) -> bool {
+            regex
+                .as_ref()
+                .is_none_or(|regex| text.is_some_and(|text| regex.is_match(text)))
+        }
+
+        text(&self.app_name, window.app_name)
+            && text(&self.title, window.title)
+            && text(&self.role, window.role)
+            && text(&self.subrole, window.subrole)
+            && self
+                .con_mark
+                .as_ref()
+                .is_none_or(|regex| window.marks.iter().any(|mark| regex.is_match(mark)))
+            && self.id.is_none_or(|id| WindowId::from(id) == window.id)
+            && self.pid.is_none_or(|pid| pid == window.pid)
+            && self.layer.is_none_or(|layer| layer == window.layer)
+            && self
+                .floating
+                .is_none_or(|floating| floating == window.floating)
+    }
+
+    /// Parse criteria given as `[<criterion>=<value> ...]`, where a value
+    /// containing spaces or `]` must be quoted and `floating` and `tiling`
+    /// take no value.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match Self::parse_leading(s)? {
+            (criteria, "") => Ok(criteria),
+            (_, rest) => Err(format!("unexpected {rest} after criteria")),
+        }
+    }
+
+    /// Parse the criteria that `s` starts with, returning them along with the
+    /// rest of `s`, trimmed.
+    pub fn parse_leading(s: &str) -> Result<(Self, &str), String> {
+        let body = s
+            .trim_start()
+            .strip_prefix('[')
+            .ok_or_else(|| format!("expected criteria in [brackets]: {s}"))?;
+
+        let mut criteria = Self::default();
+        let mut chars = body.char_indices().peekable();
+        loop {
+            while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+
+            let Some(&(start, c)) = chars.peek() else {
+                return Err(format!("unclosed criteria: {s}"));
+            };
+            if c == ']' {
+                return Ok((criteria, body[start + 1..].trim()));
+            }
+
+            let mut key = String::new();
+            while let Some((_, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                key.push(c);
+            }
+            if key.is_empty() {
+                return Err(format!("unexpected {c:?} in criteria: {s}"));
+            }
+
+            let value = match chars.next_if(|(_, c)| *c == '=') {
+                Some(_) => {
+                    Some(read_value(&mut chars).ok_or_else(|| format!("unclosed quote: {s}"))?)
+                }
+                None => None,
+            };
+            criteria.set(&key, value)?;
+        }
+    }
+
+    fn set(&mut self, key: &str, value: Option<String>) -> Result<(), String> {
+        let required = || {
+            value
+                .clone()
+                .ok_or_else(|| format!("expected a value for {key}"))
+        };
+
+        match key {
+            "app_name" => self.app_name = Some(Regex::new(&required()?)?),
+            "title" => self.title = Some(Regex::new(&required()?)?),
+            "role" => self.role = Some(Regex::new(&required()?)?),
+            "subrole" => self.subrole = Some(Regex::new(&required()?)?),
+            "con_mark" => self.con_mark = Some(Regex::new(&required()?)?),
+            "id" => self.id = Some(number(key, &required()?)?),
+            "pid" => self.pid = Some(number(key, &required()?)?),
+            "layer" => self.layer = Some(number(key, &required()?)?),
+            "floating" | "tiling" if value.is_none() => self.floating = Some(key == "floating"),
+            "floating" | "tiling" => return Err(format!("{key} takes no value")),
+            unknown => return Err(format!("unknown criterion: {unknown}")),
+        }
+        Ok(())
+    }
+}
+
+fn number<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid {key}: {value}"))
+}
+
+// Read a criterion's value, either quoted, where `\"` stands for a quote and
+// any other backslash is kept for the regex, or bare up to whitespace or the
+// closing bracket. Returns `None` if a quote is left open.
+fn read_value(chars: &mut std::iter::Peekable<std::str::CharIndices>) -> Option<String> {
+    let mut value = String::new();
+
+    if chars.next_if(|(_, c)| *c == '"').is_none() {
+        while let Some((_, c)) = chars.next_if(|(_, c)| !c.is_whitespace() && *c != ']') {
+            value.push(c);
+        }
+        return Some(value);
+    }
+
+    loop {
+        match chars.next()?.1 {
+            '"' => return Some(value),
+            '\\' if chars.next_if(|(_, c)| *c == '"').is_some() => value.push('"'),
+            c => value.push(c),
+        }
+    }
+}
+
+impl Display for Criteria {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let texts = [
+            ("app_name", &self.app_name),
+            ("title", &self.title),
+            ("role", &self.role),
+            ("subrole", &self.subrole),
+            ("con_mark", &self.con_mark),
+        ];
+        let numbers = [
+            ("id", self.id.map(i64::from)),
+            ("pid", self.pid.map(i64::from)),
+            ("layer", self.layer.map(i64::from)),
+        ];
+
+        let mut criteria: Vec<String> = texts
+            .into_iter()
+            .filter_map(|(key, regex)| {
+                let regex = regex.as_ref()?.as_str().replace('"', "\\\"");
+                Some(format!("{key}=\"{regex}\""))
+            })
+            .collect();
+        criteria.extend(
+            numbers
+                .into_iter()
+                .filter_map(|(key, n)| Some(format!("{key}={}", n?))),
+        );
+        match self.floating {
+            Some(true) => criteria.push("floating".to_string()),
+            Some(false) => criteria.push("tiling".to_string()),
+            None => {}
+        }
+
+        write!(f, "[{}]", criteria.join(" "))
+    }
+}
@@ -0,0 +1,203 @@
+use super::*;
+
+fn regex(source: &str) -> Regex {
+    Regex::new(source).unwrap()
+}
+
+fn safari(title: &'static str) -> WindowProperties<'static> {
+    WindowProperties {
+        id: WindowId::from(7u32),
+        app_name: Some("Safari"),
+        title: Some(title),
+        pid: 42,
+        layer: 0,
+        role: Some("AXWindow"),
+        subrole: Some("AXStandardWindow"),
+        floating: false,
+        marks: Vec::new(),
+    }
+}
+
+#[test]
+fn regex_matches_anywhere_unless_anchored() {
+    assert!(regex("box").is_match("Inbox"));
+    assert!(!regex("^box").is_match("Inbox"));
+    assert!(regex("^Inbox$").is_match("Inbox"));
+    assert!(!regex("^Inbox$").is_match("Inbox (3)"));
+    assert!(regex("").is_match("anything"));
+}
+
+#[test]
+fn regex_matches_classes_and_escapes() {
+    assert!(regex(r"^\d+ unread$").is_match("12 unread"));
+    assert!(!regex(r"^\d+ unread$").is_match("no unread"));
+    assert!(regex("^[A-Z][a-z]+$").is_match("Safari"));
+    assert!(!regex("^[^S]").is_match("Safari"));
+    assert!(regex(r"\(3\)").is_match("Inbox (3)"));
+    assert!(regex(r"a\.b").is_match("a.b"));
+    assert!(!regex(r"a\.b").is_match("axb"));
+    assert!(regex("[]a]").is_match("]"));
+    assert!(regex("[a-]").is_match("-"));
+    assert!(regex(r"^\w+\s\S+$").is_match("New tab"));
+}
+
+#[test]
+fn regex_backtracks_through_repeats_and_alternatives() {
+    assert!(regex("^a.*b$").is_match("a b c b"));
+    assert!(regex("^(ab)+c$").is_match("ababc"));
+    assert!(!regex("^(ab)+c$").is_match("abac"));
+    assert!(regex("^colou?r$").is_match("color"));
+    assert!(regex("^(Mail|Messages)$").is_match("Messages"));
+    assert!(!regex("^(Mail|Messages)$").is_match("MailMessages"));
+    assert!(regex("^a.*?b$").is_match("aabb"));
+    assert!(regex("^a{2,3}$").is_match("aaa"));
+    assert!(!regex("^a{2,3}$").is_match("aaaa"));
+    assert!(regex("^(a*)*$").is_match("aaa"));
+    assert!(regex(r"^x\{$").is_match("x{"));
+}
+
+#[test]
+fn regex_ignores_case_when_asked() {
+    assert!(regex("(?i)^safari$").is_match("Safari"));
+    assert!(regex("(?i)[a-z]+").is_match("SAFARI"));
+    assert!(!regex("^safari$").is_match("Safari"));
+}
+
+#[test]
+fn regex_rejects_malformed_patterns() {
+    for source in ["(ab", "ab)", "[ab", "*a", "a{3,2}", "[z-a]", "a\\", "(?=a)"] {
+        assert!(Regex::new(source).is_err(), "{source}");
+    }
+}
+
+#[test]
+fn parse_reads_values_and_flags() {
+    let criteria = Criteria::parse(r#"[app_name="Safari" title="^Inbox" floating]"#).unwrap();
+
+    assert_eq!(
+        criteria,
+        Criteria {
+            app_name: Some(regex("Safari")),
+            title: Some(regex("^Inbox")),
+            floating: Some(true),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn parse_reads_numbers_bare_values_and_escaped_quotes() {
+    let criteria =
+        Criteria::parse(r#"[ pid=42 layer=-1 id=7 con_mark=term title="say \"hi\" [1]" tiling ]"#)
+            .unwrap();
+
+    assert_eq!(criteria.pid, Some(42));
+    assert_eq!(criteria.layer, Some(-1));
+    assert_eq!(criteria.id, Some(7));
+    assert_eq!(criteria.con_mark, Some(regex("term")));
+    assert_eq!(criteria.title, Some(regex(r#"say "hi" [1]"#)));
+    assert_eq!(criteria.floating, Some(false));
+}
+
+#[test]
+fn parse_leading_returns_the_rest() {
+    let (criteria, rest) = Criteria::parse_leading(r#"[app_name="Slack"]  assign 4 "#).unwrap();
+
+    assert_eq!(criteria.app_name, Some(regex("Slack")));
+    assert_eq!(rest, "assign 4");
+    assert!(Criteria::parse("[app_name=Slack] focus").is_err());
+}
+
+#[test]
+fn parse_rejects_malformed_criteria() {
+    for s in [
+        "app_name=Safari",
+        "[app_name=Safari",
+        r#"[title="Inbox]"#,
+        "[colour=red]",
+        "[pid=forty]",
+        "[title]",
+        "[floating=yes]",
+        "[title=(]",
+        "[=Safari]",
+    ] {
+        assert!(Criteria::parse(s).is_err(), "{s}");
+    }
+}
+
+#[test]
+fn display_round_trips() {
+    let s = r#"[app_name="Safari" title="say \"hi\"" pid=42 floating]"#;
+    let criteria = Criteria::parse(s).unwrap();
+
+    assert_eq!(criteria.to_string(), s);
+    assert_eq!(Criteria::parse(&criteria.to_string()).unwrap(), criteria);
+}
+
+#[test]
+fn matches_requires_every_criterion() {
+    let criteria = Criteria::parse(r#"[app_name="^Safari$" title="^Inbox" tiling]"#).unwrap();
+
+    assert!(criteria.matches(&safari("Inbox (3)")));
+    assert!(!criteria.matches(&safari("Outbox")));
+    assert!(!criteria.matches(&WindowProperties {
+        floating: true,
+        ..safari("Inbox")
+    }));
+    assert!(!criteria.matches(&WindowProperties {
+        app_name: Some("Safari Technology Preview"),
+        ..safari("Inbox")
+    }));
+    assert!(Criteria::default().matches(&safari("Outbox")));
+}
+
+#[test]
+fn matches_numbers_and_roles() {
+    let window = safari("Inbox");
+
+    assert!(
+        Criteria::parse("[id=7 pid=42 layer=0]")
+            .unwrap()
+            .matches(&window)
+    );
+    assert!(!Criteria::parse("[id=8]").unwrap().matches(&window));
+    assert!(!Criteria::parse("[pid=43]").unwrap().matches(&window));
+    assert!(!Criteria::parse("[layer=3]").unwrap().matches(&window));
+    assert!(
+        Criteria::parse("[subrole=Standard]")
+            .unwrap()
+            .matches(&window)
+    );
+    assert!(
+        !Criteria::parse("[subrole=AXDialog]")
+            .unwrap()
+            .matches(&window)
+    );
+}
+
+#[test]
+fn missing_text_never_matches() {
+    let untitled = WindowProperties {
+        title: None,
+        ..safari("")
+    };
+
+    assert!(!Criteria::parse("[title=.*]").unwrap().matches(&untitled));
+    assert!(Criteria::parse("[app_name=.*]").unwrap().matches(&untitled));
+}
+
+#[test]
+fn con_mark_matches_any_mark() {
+    let criteria = Criteria::parse("[con_mark=^term$]").unwrap();
+    let marked = WindowProperties {
+        marks: vec!["editor", "term"],
+        ..safari("Inbox")
+    };
+
+    assert!(criteria.matches(&marked));
+    assert!(!criteria.matches(&WindowProperties {
+        marks: vec!["terminal"],
+        ..safari("Inbox")
+    }));
+    assert!(!criteria.matches(&safari("Inbox")));
+}
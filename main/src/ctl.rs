@@ -1,4 +1,6 @@
 use crate::config::Config;
+use crate::criteria::Criteria;
+use core_graphics::Direction;
 use serde::{Deserialize, Serialize};
 
 pub const CTL_SOCK: &str = "/tmp/i3mac/ctl.sock";
@@ -6,9 +8,179 @@ pub const CTL_SOCK: &str = "/tmp/i3mac/ctl.sock";
 #[derive(Debug, Serialize, Deserialize)]
 pub enum CtlToWmMessage {
     GetConfig,
+    GetMarks,
+    Command(Command),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum WmToCtlMessage {
     Config(Config),
+    /// Every mark currently set on a window, sorted by name.
+    Marks(Vec<String>),
+    Done,
+    Failed(String),
+}
+
+/// A command for the window manager to run against its currently focused
+/// window, in the style of `i3-msg`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Command {
+    Layout(LayoutArg),
+    Focus(FocusArg),
+    Balance(BalanceArg),
+    Resize(ResizeArg),
+    Fullscreen(FullscreenArg),
+    Scratchpad(ScratchpadArg),
+    /// Mark the focused window with a name, taking the name from any window
+    /// that already has it.
+    Mark(MarkArg),
+    /// Remove a mark from whichever window has it, or every mark when no name
+    /// is given.
+    Unmark(Option<String>),
+    /// Focus a window matching criteria, wherever it is.
+    FocusMatching(Box<Criteria>),
+    /// Move the focused window beside the window with a mark.
+    MoveToMark(String),
+    Workspace(WorkspaceArg),
+    /// Focus the most recently focused window other than the focused one, on
+    /// any logical or physical display.
+    FocusLast,
+    /// Keep the focused floating window visible on every logical display of
+    /// its physical display.
+    Sticky(StickyArg),
+    /// Move the focused window in a direction, in the style of i3's
+    /// `move <direction> [<n> px]`. A tiled window swaps places as with the
+    /// move hotkey, and a floating window moves by the pixels given, or the
+    /// configured move amount.
+    Move(MoveArg),
+    /// Place the focused floating window within its physical display, in the
+    /// style of i3's `move position center`.
+    MovePosition(PositionArg),
+    /// Undo the last change to the active logical display's layout.
+    Undo,
+    /// Redo the last undone change to the active logical display's layout.
+    Redo,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum LayoutArg {
+    Tiled,
+    Tabbed,
+    Stacking,
+    Toggle,
+    ToggleSplit,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum FocusArg {
+    Parent,
+    Child,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum DirectionArg {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl From<DirectionArg> for Direction {
+    fn from(arg: DirectionArg) -> Self {
+        match arg {
+            DirectionArg::Left => Self::Left,
+            DirectionArg::Right => Self::Right,
+            DirectionArg::Up => Self::Up,
+            DirectionArg::Down => Self::Down,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct MoveArg {
+    pub direction: DirectionArg,
+    pub px: Option<f64>,
+}
+
+/// Where to place a floating window within the part of its physical display
+/// between the menu bar and the status bar.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum PositionArg {
+    /// Centre the window, keeping its size.
+    Centre,
+    /// Move the window against an edge, keeping its size.
+    Edge(DirectionArg),
+    /// Fill the half of the display against an edge.
+    Half(DirectionArg),
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum BalanceArg {
+    /// The focused window's container.
+    Container,
+    /// Every container on the active logical display.
+    Display,
+}
+
+/// A resize in the style of i3's `resize grow|shrink width|height <n> px or
+/// <m> ppt` and `resize set <width> <height>`.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum ResizeArg {
+    Grow(Dimension, ResizeAmount),
+    Shrink(Dimension, ResizeAmount),
+    Set {
+        width: Option<ResizeAmount>,
+        height: Option<ResizeAmount>,
+    },
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum Dimension {
+    Width,
+    Height,
+}
+
+/// An amount given in pixels, percentage points of the parent container, or
+/// both.
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
+pub struct ResizeAmount {
+    pub px: Option<f64>,
+    pub ppt: Option<f64>,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum FullscreenArg {
+    Enable,
+    Disable,
+    Toggle,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum StickyArg {
+    Enable,
+    Disable,
+    Toggle,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum ScratchpadArg {
+    /// Hide the focused window in the scratchpad.
+    Move,
+    /// Show the next scratchpad window, or hide the one shown.
+    Show,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkArg {
+    pub name: String,
+    /// Keep the window's other marks, rather than replacing them.
+    pub add: bool,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum WorkspaceArg {
+    /// Switch to the logical display with this number.
+    Number(usize),
+    /// Switch back to the logical display active before the current one.
+    BackAndForth,
 }
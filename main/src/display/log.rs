@@ -1,6 +1,6 @@
 use crate::display::physical;
 use crate::{
-    container::Axis,
+    container::{Axis, Layout, Resize},
     display::logical,
     log::{Level, Log},
 };
@@ -14,6 +14,18 @@ pub enum Message {
     LogicalSetFocused(WindowId),
     LogicalAddedWindow(WindowId),
     LogicalResizeWindow(WindowId, Direction),
+    LogicalSetLayout(Layout, WindowId),
+    LogicalMoveWindow(WindowId, Direction),
+    LogicalFocusParent(usize),
+    LogicalFocusChild(usize),
+    LogicalBalance(WindowId),
+    LogicalBalanceAll,
+    LogicalResize(Resize, WindowId),
+    LogicalFocusEdge(Direction, WindowId),
+    LogicalSetFullscreen(Option<WindowId>),
+    LogicalUndo(WindowId),
+    LogicalRedo(WindowId),
+    LogicalRestoredWindow(WindowId),
 
     PhysicalNew,
     PhysicalShiftFocus(Direction, WindowId),
@@ -28,9 +40,23 @@ pub enum Message {
     PhysicalSwitchActive(logical::Id),
     PhysicalResizeFocused(Direction),
     PhysicalSwitchDisplay(logical::Id),
+    PhysicalSetLayout(Layout),
+    PhysicalMoveFocused(Direction),
+    PhysicalFocusParent,
+    PhysicalFocusChild,
+    PhysicalBalance,
+    PhysicalBalanceAll,
+    PhysicalResize(Resize),
+    PhysicalFocusEdge(Direction, WindowId),
+    PhysicalSetFullscreen(bool),
+    PhysicalUndo,
+    PhysicalSetSticky(WindowId, bool),
+    PhysicalRedo,
+    PhysicalRestoredWindow(WindowId),
 
     FocusLogical(logical::Id, WindowId),
     Split(Axis),
+    SetLayout(Layout),
     SetActivePhysical(physical::Id),
     SwitchToLogical(physical::Id, logical::Id),
     RemovedEmptyLogical(logical::Id),
@@ -42,6 +68,9 @@ pub enum Message {
     CouldNotFitWindow(WindowId, logical::Id),
     AddedWindow(WindowId, logical::Id),
     RemovedWindow(WindowId, physical::Id),
+    RestoredWindow(WindowId, logical::Id),
+    ShiftFocusToPhysical(Direction, physical::Id),
+    RecordedFocus(WindowId),
 }
 
 impl Log for Message {
@@ -56,6 +85,18 @@ impl Log for Message {
             LogicalSetFocused(_) => Level::Trace,
             LogicalAddedWindow(_) => Level::Info,
             LogicalResizeWindow(_, _) => Level::Trace,
+            LogicalSetLayout(_, _) => Level::Trace,
+            LogicalMoveWindow(_, _) => Level::Trace,
+            LogicalFocusParent(_) => Level::Trace,
+            LogicalFocusChild(_) => Level::Trace,
+            LogicalBalance(_) => Level::Trace,
+            LogicalBalanceAll => Level::Trace,
+            LogicalResize(_, _) => Level::Trace,
+            LogicalFocusEdge(_, _) => Level::Trace,
+            LogicalSetFullscreen(_) => Level::Trace,
+            LogicalUndo(_) => Level::Trace,
+            LogicalRedo(_) => Level::Trace,
+            LogicalRestoredWindow(_) => Level::Info,
 
             PhysicalNew => Level::Info,
             PhysicalShiftFocus(_, _) => Level::Trace,
@@ -70,9 +111,23 @@ impl Log for Message {
             PhysicalSwitchActive(_) => Level::Trace,
             PhysicalResizeFocused(_) => Level::Trace,
             PhysicalSwitchDisplay(_) => Level::Trace,
+            PhysicalSetLayout(_) => Level::Trace,
+            PhysicalMoveFocused(_) => Level::Trace,
+            PhysicalFocusParent => Level::Trace,
+            PhysicalFocusChild => Level::Trace,
+            PhysicalBalance => Level::Trace,
+            PhysicalBalanceAll => Level::Trace,
+            PhysicalResize(_) => Level::Trace,
+            PhysicalFocusEdge(_, _) => Level::Trace,
+            PhysicalSetFullscreen(_) => Level::Trace,
+            PhysicalUndo => Level::Trace,
+            PhysicalSetSticky(_, _) => Level::Trace,
+            PhysicalRedo => Level::Trace,
+            PhysicalRestoredWindow(_) => Level::Info,
 
             FocusLogical(_, _) => Level::Info,
             Split(_) => Level::Info,
+            SetLayout(_) => Level::Info,
             SetActivePhysical(_) => Level::Info,
             SwitchToLogical(_, _) => Level::Info,
             RemovedEmptyLogical(_) => Level::Info,
@@ -84,6 +139,9 @@ impl Log for Message {
             CouldNotFitWindow(_, _) => Level::Info,
             AddedWindow(_, _) => Level::Info,
             RemovedWindow(_, _) => Level::Info,
+            RestoredWindow(_, _) => Level::Info,
+            ShiftFocusToPhysical(_, _) => Level::Info,
+            RecordedFocus(_) => Level::Trace,
         }
     }
 
@@ -106,6 +164,35 @@ impl Log for Message {
             LogicalResizeWindow(window, direction) => {
                 format!("resize window {window} in {direction}")
             }
+            LogicalSetLayout(layout, window) => {
+                format!("set layout of container that owns window {window} to {layout}")
+            }
+            LogicalMoveWindow(window, direction) => {
+                format!("moved window {window} {direction} within the tree")
+            }
+            LogicalFocusParent(depth) => {
+                format!("focused parent container, {depth} levels above the focused window")
+            }
+            LogicalFocusChild(depth) => {
+                format!("focused child container, {depth} levels above the focused window")
+            }
+            LogicalBalance(window) => {
+                format!("balanced container that owns window {window}")
+            }
+            LogicalBalanceAll => "balanced every container".to_string(),
+            LogicalResize(resize, window) => {
+                format!("applied {resize:?} to container that owns window {window}")
+            }
+            LogicalFocusEdge(direction, window) => {
+                format!("wrapped focus to window {window} at the {direction} edge")
+            }
+            LogicalSetFullscreen(Some(window)) => format!("made window {window} fullscreen"),
+            LogicalSetFullscreen(None) => "left fullscreen".to_string(),
+            LogicalUndo(window) => format!("undid last change, focusing window {window}"),
+            LogicalRedo(window) => format!("redid last undone change, focusing window {window}"),
+            LogicalRestoredWindow(window) => {
+                format!("restored window {window} to its last place in the tree")
+            }
 
             PhysicalNew => "physical display created".to_string(),
             PhysicalShiftFocus(direction, window_id) => {
@@ -126,11 +213,29 @@ impl Log for Message {
             PhysicalSwitchActive(active) => format!("switching active display {active}"),
             PhysicalResizeFocused(direction) => format!("resized focused window {direction}"),
             PhysicalSwitchDisplay(display) => format!("switching to {display:?}"),
+            PhysicalSetLayout(layout) => format!("set container layout to {layout}"),
+            PhysicalMoveFocused(direction) => format!("moved focused window {direction}"),
+            PhysicalFocusParent => "focused parent container".to_string(),
+            PhysicalFocusChild => "focused child container".to_string(),
+            PhysicalBalance => "balanced focused container".to_string(),
+            PhysicalBalanceAll => "balanced active logical display".to_string(),
+            PhysicalResize(resize) => format!("applied {resize:?} to focused container"),
+            PhysicalFocusEdge(direction, window) => {
+                format!("wrapped focus to window {window} at the {direction} edge")
+            }
+            PhysicalSetFullscreen(true) => "made focused window fullscreen".to_string(),
+            PhysicalSetFullscreen(false) => "left fullscreen".to_string(),
+            PhysicalUndo => "undid last change on active logical display".to_string(),
+            PhysicalSetSticky(window, true) => format!("made window {window} sticky"),
+            PhysicalSetSticky(window, false) => format!("made window {window} no longer sticky"),
+            PhysicalRedo => "redid last undone change on active logical display".to_string(),
+            PhysicalRestoredWindow(window) => format!("restored window {window}"),
 
             FocusLogical(logical, window) => {
                 format!("focus window {window} on logical display {logical:?}")
             }
             Split(axis) => format!("split focused container along {axis:?}"),
+            SetLayout(layout) => format!("set focused container layout to {layout:?}"),
             SetActivePhysical(physical) => format!("set display {physical} active"),
             SwitchToLogical(physical, logical) => format!("switching to {logical:?} on {physical}"),
             RemovedEmptyLogical(logical) => format!("removed empty logical display {logical:?}"),
@@ -148,6 +253,11 @@ impl Log for Message {
             }
             AddedWindow(window, logical) => format!("added window {window} to {logical:?}"),
             RemovedWindow(window, physical) => format!("removed window {window} from {physical}"),
+            RestoredWindow(window, logical) => format!("restored window {window} to {logical:?}"),
+            ShiftFocusToPhysical(direction, physical) => {
+                format!("shifted focus {direction} to {physical}")
+            }
+            RecordedFocus(window) => format!("recorded focus of window {window}"),
         }
     }
 }
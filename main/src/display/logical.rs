@@ -1,8 +1,13 @@
 use crate::{
-    container::{self, Axis, Container, split::Split},
+    config::{DEFAULT_MIN_WINDOW_SIZE, DEFAULT_RESIZE_AMOUNT},
+    container::{
+        self, Axis, Container, Layout, LayoutChange, MoveResult, Resize, TitleStrip, split::Split,
+    },
     display::log::Message::{
-        LogicalAddedWindow, LogicalNew, LogicalResizeWindow, LogicalSetFocused, LogicalShiftFocus,
-        LogicalSplitContainer,
+        LogicalAddedWindow, LogicalBalance, LogicalBalanceAll, LogicalFocusChild, LogicalFocusEdge,
+        LogicalFocusParent, LogicalMoveWindow, LogicalNew, LogicalRedo, LogicalResize,
+        LogicalResizeWindow, LogicalRestoredWindow, LogicalSetFocused, LogicalSetFullscreen,
+        LogicalSetLayout, LogicalShiftFocus, LogicalSplitContainer, LogicalUndo,
     },
     error::{Error, Result},
     log::{Level, Log, Logger, Prefix},
@@ -10,10 +15,30 @@ use crate::{
 };
 use core_graphics::{Bounds, Direction, WindowId};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     fmt::Debug,
 };
 
+/// How many tree-changing operations a logical display can undo.
+const HISTORY_LIMIT: usize = 32;
+
+/// The part of a screen with the given Core Graphics bounds that windows may
+/// occupy.
+pub fn usable_bounds(cg_bounds: Bounds) -> Bounds {
+    // Core Graphics bounds -- the bounds used for a `physical::Display` do
+    // not include the Apple menu bar so we need to subtract it to get the
+    // usable area for windows to exist in.
+    const MENU_BAR_HEIGHT: f64 = 37.0;
+
+    // We also subtract the height of the i3-style `StatusBar` added to the
+    // bottom of the screen.
+    Bounds {
+        height: cg_bounds.height - MENU_BAR_HEIGHT - StatusBar::HEIGHT,
+        y: cg_bounds.y + MENU_BAR_HEIGHT,
+        ..cg_bounds
+    }
+}
+
 #[derive(PartialEq, Eq, Copy, Clone, Hash, Ord, PartialOrd)]
 pub struct Id(pub usize);
 
@@ -45,6 +70,8 @@ impl Id {
 pub struct Config {
     window_padding: Option<f64>,
     log_level: Level,
+    resize_amount: Option<f64>,
+    min_window_size: Option<f64>,
 }
 
 impl From<crate::display::physical::Config> for Config {
@@ -52,6 +79,8 @@ impl From<crate::display::physical::Config> for Config {
         Self {
             window_padding: config.window_padding,
             log_level: config.log_level,
+            resize_amount: config.resize_amount,
+            min_window_size: config.min_window_size,
         }
     }
 }
@@ -60,6 +89,14 @@ impl Config {
     pub fn window_padding(&self) -> f64 {
         self.window_padding.unwrap_or_default()
     }
+
+    pub fn resize_amount(&self) -> f64 {
+        self.resize_amount.unwrap_or(DEFAULT_RESIZE_AMOUNT)
+    }
+
+    pub fn min_window_size(&self) -> f64 {
+        self.min_window_size.unwrap_or(DEFAULT_MIN_WINDOW_SIZE)
+    }
 }
 
 #[derive(Debug)]
@@ -75,7 +112,32 @@ pub struct NoWindows {
 
 pub struct SomeWindows {
     focused_window: WindowId,
+    /// How many levels above the focused window's leaf the selection rests, as
+    /// moved by `focus_parent` and `focus_child`. At 0 the focused window
+    /// itself is selected; otherwise a split containing it is.
+    focus_depth: usize,
+    /// The window given the whole of the logical display's bounds, in front of
+    /// the rest of its windows, which keep their layout underneath. The
+    /// window keeps its place in the container tree meanwhile.
+    fullscreen: Option<WindowId>,
+    root: Container,
+    history: History,
+}
+
+/// The container tree and focus of a logical display at some point in time.
+#[derive(Clone)]
+struct Snapshot {
     root: Container,
+    focused_window: WindowId,
+    focus_depth: usize,
+}
+
+/// Snapshots taken before tree-changing operations, most recent last, and
+/// those taken before undoing them.
+#[derive(Default)]
+struct History {
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
 }
 
 impl Display<NoWindows> {
@@ -85,18 +147,7 @@ impl Display<NoWindows> {
     /// for the Apple menu bar at the top of the screen, and i3mac's status bar
     /// at the bottom of the screen.
     pub(crate) fn new(id: Id, cg_bounds: Bounds, config: Config) -> Self {
-        // Core Graphics bounds -- the bounds used for a `physical::Display` do
-        // not include the Apple menu bar so we need to subtract it to get the
-        // usable area for windows to exist in.
-        const MENU_BAR_HEIGHT: f64 = 37.0;
-
-        // We also subtract the height of the i3-style `StatusBar` added to the
-        // bottom of the screen.
-        let bounds = Bounds {
-            height: cg_bounds.height - MENU_BAR_HEIGHT - StatusBar::HEIGHT,
-            y: cg_bounds.y + MENU_BAR_HEIGHT,
-            ..cg_bounds
-        };
+        let bounds = usable_bounds(cg_bounds);
 
         let mut logger =
             Logger::try_new("/dev/stdout", config.log_level, id.as_log_prefix()).unwrap();
@@ -118,13 +169,17 @@ impl Display<NoWindows> {
             vec![],
         ));
         root.add_window(window, self.config.window_padding())?;
+        root.record_focus(window.id);
 
         let ret = Display::<SomeWindows> {
             config: self.config,
             logger: self.logger,
             state: SomeWindows {
                 focused_window: window.id,
+                focus_depth: 0,
+                fullscreen: None,
                 root,
+                history: History::default(),
             },
         };
 
@@ -139,55 +194,97 @@ impl Display<SomeWindows> {
     }
 
     /// Shift focus within the logical display's managed windows in some
-    /// direction. If the focus cannot shift any more in the provided
-    /// `direction`, focus remains on the currently focused window.
+    /// direction, returning the newly focused window. If there is no window
+    /// in the provided `direction`, focus remains on the currently focused
+    /// window and `None` is returned.
     // In order to switch focus in some direction:
-    //  - If there are no windows at all (including the one that should be
-    //    currently focused), return some error.
-    //  - Create a list of all window, sorted by either x or y position, based
-    //    on the given direction.
-    //  - Find the focused window in this list.
-    //  - If there are no more windows in the direction of the shift, return.
-    //  - If there is one, focus it and return.
-    pub fn shift_focus(&mut self, direction: Direction) -> Result<WindowId> {
-        use Direction::*;
-
-        // Declared with a block to drop mutability after sorting.
-        let windows: Vec<_> = {
-            let mut windows: Vec<_> = self.window_bounds().into_iter().collect();
-            if windows.is_empty() {
-                return Err(Error::CannotFocusEmptyDisplay);
-            }
-
-            match direction {
-                Left | Right => windows.sort_by(|a, b| a.1.x.total_cmp(&b.1.x)),
-                Up | Down => windows.sort_by(|a, b| a.1.y.total_cmp(&b.1.y)),
-            };
-
-            windows
+    //  - If the focused window is in a tabbed split with a neighbouring tab in
+    //    the given direction, focus that tab and return.
+    //  - Otherwise take the bounds of every visible window; tabs hidden behind
+    //    the visible one are skipped.
+    //  - Of the windows lying wholly beyond the focused window in the given
+    //    direction and overlapping it on the perpendicular axis, focus the
+    //    nearest.
+    pub fn shift_focus(&mut self, direction: Direction) -> Result<Option<WindowId>> {
+        let focused_window = self.state.focused_window;
+
+        let next_focus = match self.state.root.neighbouring_tab(focused_window, direction) {
+            Some(tab) => tab,
+            None => match self
+                .state
+                .root
+                .window_in_direction(focused_window, direction)
+            {
+                Some(window) => window,
+                None => return Ok(None),
+            },
         };
 
-        let current_focussed_index = windows
-            .iter()
-            .position(|(id, _)| *id == self.state.focused_window)
-            .unwrap_or(0);
+        self.focus(next_focus);
+        LogicalShiftFocus(direction, next_focus).log(&mut self.logger);
+        Ok(Some(next_focus))
+    }
 
-        let next_focus = match (current_focussed_index, direction) {
-            (n, Left | Up) if n != 0 => windows[n - 1].0,
-            (n, Right | Down) if n < windows.len() - 1 => windows[n + 1].0,
-            _ => windows[current_focussed_index].0,
-        };
+    /// Focus the visible window furthest from the focused window in
+    /// `direction`, as when focus wraps around from the opposite edge of the
+    /// screen, returning the newly focused window. Focus stays put when the
+    /// focused window is already at that edge.
+    pub fn focus_edge(&mut self, direction: Direction) -> WindowId {
+        if let Some(window) = self
+            .state
+            .root
+            .window_at_edge(self.state.focused_window, direction)
+        {
+            self.focus(window);
+            LogicalFocusEdge(direction, window).log(&mut self.logger);
+        }
 
-        self.state.focused_window = next_focus;
+        self.state.focused_window
+    }
 
-        LogicalShiftFocus(direction, next_focus).log(&mut self.logger);
-        Ok(next_focus)
+    /// Focus `window`, selecting the window itself and recording it in the
+    /// focus stack of every split above it.
+    // Focusing another window brings it in front of any fullscreen one, so
+    // the fullscreen window returns to its tiled bounds.
+    fn focus(&mut self, window: WindowId) {
+        if self.state.fullscreen.is_some_and(|w| w != window) {
+            self.set_fullscreen(false);
+        }
+        self.state.focused_window = window;
+        self.state.focus_depth = 0;
+        self.state.root.record_focus(window);
     }
 
     /// Returns a map of window ID to its bounds for all windows the logical
-    /// display manages.
+    /// display manages. A fullscreen window is given the logical display's
+    /// whole bounds.
     pub fn window_bounds(&self) -> HashMap<WindowId, Bounds> {
-        self.state.root.window_bounds_by_id()
+        let mut bounds = self.state.root.window_bounds_by_id();
+        if let Some(window) = self.state.fullscreen {
+            bounds.insert(window, self.state.root.bounds());
+        }
+        bounds
+    }
+
+    /// Returns the title strips of every visible stacked split on the logical
+    /// display, or none while a window is fullscreen in front of them.
+    pub fn title_strips(&self) -> Vec<TitleStrip> {
+        if self.state.fullscreen.is_some() {
+            return Vec::new();
+        }
+        self.state.root.title_strips(self.state.focused_window)
+    }
+
+    /// Whether a window is fullscreen on the logical display.
+    pub fn is_fullscreen(&self) -> bool {
+        self.state.fullscreen.is_some()
+    }
+
+    /// Give the focused window the logical display's whole bounds, or return
+    /// the fullscreen window to its tiled bounds.
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        self.state.fullscreen = fullscreen.then_some(self.state.focused_window);
+        LogicalSetFullscreen(self.state.fullscreen).log(&mut self.logger);
     }
 
     /// Returns the set of all window IDs the logical display manages.
@@ -195,27 +292,151 @@ impl Display<SomeWindows> {
         self.state.root.window_ids()
     }
 
-    /// Split the logical display's focused window's container along the
-    /// provided `axis`.
+    /// Split the logical display's selected container along the provided
+    /// `axis`.
+    // To split the selection:
+    //  - If it is the focused window, wrap it in a new split along `axis` so
+    //    the next window opened lands beside it, unless it is alone in its
+    //    split, in which case that split just changes axis.
+    //  - If it is a split with a single child, change its axis.
+    //  - If it is a split with several children, wrap the whole split in a new
+    //    split along `axis`.
     pub fn split(&mut self, axis: Axis) -> Result<()> {
-        // Safety: If we are a Display::<SomeWindows> then there is guaranteed
-        //         to be a focused window and that windows is guaranteed to have
-        //         a parent.
-        let container = self
-            .state
+        let focused_window = self.state.focused_window;
+        let before = self.snapshot();
+
+        if self.state.focus_depth == 0 {
+            self.state.root.split_window(focused_window, axis)?;
+        } else {
+            let container = self.selected_mut()?;
+            match container {
+                Container::Split(split) if split.children.len() >= 2 => container.wrap(axis),
+                _ => container.split(axis)?,
+            }
+        }
+
+        self.push_undo(before);
+        LogicalSplitContainer(axis, focused_window).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Return the logical display's selected container.
+    fn selected_mut(&mut self) -> Result<&mut Container> {
+        self.state
             .root
-            .parent_leaf_of_window_mut(self.state.focused_window)
-            .unwrap();
+            .selected_mut(self.state.focused_window, self.state.focus_depth)
+            .ok_or(Error::CannotFindSelection)
+    }
+
+    /// Move the selection to the split containing it, in the style of i3's
+    /// `focus parent`.
+    pub fn focus_parent(&mut self) -> Result<()> {
+        let max_depth = self.root_depth();
+        if self.state.focus_depth >= max_depth {
+            return Err(Error::CannotFocusParentOfRoot);
+        }
 
-        LogicalSplitContainer(axis, self.state.focused_window).log(&mut self.logger);
-        container.split(axis)
+        self.state.focus_depth += 1;
+        LogicalFocusParent(self.state.focus_depth).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Move the selection back down towards the focused window, in the style
+    /// of i3's `focus child`.
+    pub fn focus_child(&mut self) -> Result<()> {
+        if self.state.focus_depth == 0 {
+            return Err(Error::CannotFocusChildOfWindow);
+        }
+
+        self.state.focus_depth -= 1;
+        LogicalFocusChild(self.state.focus_depth).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// How many levels above the focused window's leaf the root sits.
+    fn root_depth(&self) -> usize {
+        // Safety: the focused window is always managed by the root.
+        self.state
+            .root
+            .depth_of_window(self.state.focused_window)
+            .unwrap()
+    }
+
+    /// Returns every window in the logical display's selected container, in
+    /// tree order.
+    pub fn selected_windows(&self) -> Vec<container::Window> {
+        self.state
+            .root
+            .selected(self.state.focused_window, self.state.focus_depth)
+            .map(Container::windows)
+            .unwrap_or_default()
+    }
+
+    /// Change the layout of the logical display's selected split, or the split
+    /// that owns its focused window when the window itself is selected,
+    /// returning the layout it now has.
+    pub fn set_layout(&mut self, change: LayoutChange) -> Result<Layout> {
+        let padding = self.config.window_padding();
+        let before = self.snapshot();
+        let layout = self.selected_split_mut()?.change_layout(change, padding)?;
+        self.push_undo(before);
+
+        LogicalSetLayout(layout, self.state.focused_window).log(&mut self.logger);
+        Ok(layout)
+    }
+
+    /// Reset the logical display's selected split, or the split that owns its
+    /// focused window when the window itself is selected, to equal shares.
+    pub fn balance(&mut self) -> Result<()> {
+        let padding = self.config.window_padding();
+        let before = self.snapshot();
+        self.selected_split_mut()?.balance(padding)?;
+        self.push_undo(before);
+
+        LogicalBalance(self.state.focused_window).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Reset every split in the logical display to equal shares.
+    pub fn balance_all(&mut self) -> Result<()> {
+        let before = self.snapshot();
+        let Container::Split(root) = &mut self.state.root else {
+            return Err(Error::ExpectedSplitContainer);
+        };
+        root.balance(self.config.window_padding())?;
+        self.push_undo(before);
+
+        LogicalBalanceAll.log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Return the split that layout commands act on: the selected split, or
+    /// the split that owns the focused window when the window itself is
+    /// selected.
+    fn selected_split_mut(&mut self) -> Result<&mut Split> {
+        let focused_window = self.state.focused_window;
+
+        let container = if self.state.focus_depth == 0 {
+            self.state.root.get_parent_of_window_mut(focused_window)
+        } else {
+            self.state
+                .root
+                .selected_mut(focused_window, self.state.focus_depth)
+        };
+
+        match container {
+            Some(Container::Split(split)) => Ok(split),
+            _ => Err(Error::ExpectedSplitContainer),
+        }
     }
 
     /// Set the logical display's focussed window to `window_id` or return an
     /// error if the logical display does not manage the window.
     pub fn set_focused_window(&mut self, window_id: WindowId) -> Result<()> {
         if self.window_ids().contains(&window_id) {
-            self.state.focused_window = window_id;
+            if window_id != self.state.focused_window {
+                self.focus(window_id);
+            }
             LogicalSetFocused(window_id).log(&mut self.logger);
             Ok(())
         } else {
@@ -225,6 +446,7 @@ impl Display<SomeWindows> {
 
     pub fn remove_window(self, window_id: WindowId) -> Result<RemoveResult> {
         let mut root = self.state.root;
+        let successor = root.focus_successor(window_id);
 
         match root.remove_window(window_id, self.config.window_padding())? {
             container::RemoveResult::NotFound => Err(Error::WindowNotFound),
@@ -236,21 +458,33 @@ impl Display<SomeWindows> {
                 },
             })),
             container::RemoveResult::Removed => {
-                let new_focused = if self.state.focused_window == window_id {
-                    // Safety: since the remove result was not BecomeEmpty, we
-                    //         know there is at least one more window to become
-                    //         the newly focused one.
-                    root.window_ids().iter().next().copied().unwrap()
+                let (new_focused, focus_depth) = if self.state.focused_window == window_id {
+                    // Safety: since the remove result was not BecomeEmpty, the
+                    //         removed window had a sibling somewhere above it
+                    //         to hand focus to.
+                    let successor = successor.unwrap();
+                    root.record_focus(successor);
+                    (successor, 0)
                 } else {
-                    self.state.focused_window
+                    (self.state.focused_window, self.state.focus_depth)
                 };
 
+                // Removing a window can collapse splits above the focused
+                // window, so keep the selection within the tree.
+                // Safety: the focused window was not removed from the root.
+                let focus_depth = focus_depth.min(root.depth_of_window(new_focused).unwrap());
+
+                let fullscreen = self.state.fullscreen.filter(|&w| w != window_id);
+
                 Ok(RemoveResult::StillHasWindows(Display {
                     config: self.config,
                     logger: self.logger,
                     state: SomeWindows {
                         focused_window: new_focused,
+                        focus_depth,
+                        fullscreen,
                         root,
+                        history: self.state.history,
                     },
                 }))
             }
@@ -270,44 +504,421 @@ impl Display<SomeWindows> {
     // If there is no window:
     //  - Add new window as a child of the root (horizontal split)
     pub fn add_window(&mut self, window: container::Window) -> Result<()> {
-        // Safety: If we are a Display::<SomeWindows> then there is guaranteed
-        //         to be a focused window and that windows is guaranteed to have
-        //         a parent.
-        let container = self
-            .state
-            .root
-            .get_parent_of_window_mut(self.state.focused_window)
-            .unwrap();
-
-        container.add_window(window, self.config.window_padding())?;
+        self.state.root.add_window_beside(
+            self.state.focused_window,
+            window,
+            self.config.window_padding(),
+        )?;
         LogicalAddedWindow(window.id).log(&mut self.logger);
 
-        self.state.focused_window = window.id;
+        self.focus(window.id);
+        LogicalSetFocused(window.id).log(&mut self.logger);
+
+        Ok(())
+    }
+
+    /// Return where `window_id` sits in the logical display's tree.
+    pub fn position_of_window(&self, window_id: WindowId) -> Option<container::TreePosition> {
+        self.state.root.position_of_window(window_id)
+    }
+
+    /// Put a window back where `position` says it sat in the logical display's
+    /// tree, and focus it. Errors if none of the windows it sat beside remain,
+    /// or if it no longer fits.
+    pub fn restore_window(&mut self, position: &container::TreePosition) -> Result<()> {
+        let window = position.window;
+        self.state
+            .root
+            .restore_window(position, self.config.window_padding())?;
+        LogicalRestoredWindow(window.id).log(&mut self.logger);
+
+        self.focus(window.id);
         LogicalSetFocused(window.id).log(&mut self.logger);
 
         Ok(())
     }
 
-    /// If there is a focussed window, resize it in `direction` by the
+    /// Move the logical display's selected container one place in `direction`
+    /// within its container tree. Moving a container that is already at the
+    /// edge of the tree is a no-op.
+    pub fn move_focused_window(&mut self, direction: Direction) -> Result<()> {
+        let padding = self.config.window_padding();
+        let window = self.state.focused_window;
+        let depth = self.state.focus_depth;
+        let before = self.snapshot();
+
+        if self
+            .state
+            .root
+            .move_container(window, depth, direction, padding)?
+            == MoveResult::Moved
+        {
+            // Moving can collapse splits above the focused window, so keep the
+            // selection within the tree.
+            self.state.focus_depth = depth.min(self.root_depth());
+            self.push_undo(before);
+            LogicalMoveWindow(window, direction).log(&mut self.logger);
+        }
+
+        Ok(())
+    }
+
+    /// Resize the logical display's selected container in `direction` by the
     /// configured amount, accounting for any padding.
     pub fn resize_focused_window(&mut self, direction: Direction) -> Result<()> {
-        self.resize_window_in_direction(self.state.focused_window, direction)?;
+        if self.state.focus_depth >= self.root_depth() {
+            return Err(Error::CannotResizeRoot);
+        }
+
+        let before = self.snapshot();
+        self.state.root.resize_container(
+            self.state.focused_window,
+            self.state.focus_depth,
+            direction,
+            self.config.resize_amount(),
+            self.config.min_window_size(),
+            self.config.window_padding(),
+        )?;
+        self.push_undo(before);
         LogicalResizeWindow(self.state.focused_window, direction).log(&mut self.logger);
 
         Ok(())
     }
 
-    /// Resize the window corresponding to `id` in `direction` by the configured
-    /// amount, accounting for any padding.
-    pub fn resize_window_in_direction(&mut self, id: WindowId, direction: Direction) -> Result<()> {
-        let padding = self.config.window_padding();
-        self.state.root.resize_window(id, direction, padding)?;
-        LogicalResizeWindow(id, direction).log(&mut self.logger);
+    /// Apply `resize` to the logical display's selected container, using the
+    /// configured amount when it gives none.
+    pub fn resize_focused(&mut self, resize: Resize) -> Result<()> {
+        if self.state.focus_depth >= self.root_depth() {
+            return Err(Error::CannotResizeRoot);
+        }
+
+        let before = self.snapshot();
+        self.state.root.apply_resize(
+            self.state.focused_window,
+            self.state.focus_depth,
+            resize,
+            self.config.resize_amount(),
+            self.config.min_window_size(),
+            self.config.window_padding(),
+        )?;
+        self.push_undo(before);
+        LogicalResize(resize, self.state.focused_window).log(&mut self.logger);
+
         Ok(())
     }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            root: self.state.root.clone(),
+            focused_window: self.state.focused_window,
+            focus_depth: self.state.focus_depth,
+        }
+    }
+
+    /// Remember the tree and focus as they are now, before some operation
+    /// changes them from outside the logical display, such as taking a window
+    /// away from it.
+    pub fn checkpoint(&mut self) {
+        self.push_undo(self.snapshot());
+    }
+
+    /// Remember `before` as the state to undo to. A new operation forgets
+    /// anything undone, since it can no longer be redone on top of it.
+    fn push_undo(&mut self, before: Snapshot) {
+        self.remember(before);
+        self.state.history.redo.clear();
+    }
+
+    /// Push a snapshot onto the undo history, forgetting the oldest once the
+    /// history is full.
+    fn remember(&mut self, snapshot: Snapshot) {
+        let undo = &mut self.state.history.undo;
+        if undo.len() == HISTORY_LIMIT {
+            undo.pop_front();
+        }
+        undo.push_back(snapshot);
+    }
+
+    /// Restore the tree and focus from before the last tree-changing
+    /// operation, returning the windows that come back from elsewhere. `open`
+    /// holds every window that still exists.
+    pub fn undo(&mut self, open: &HashSet<WindowId>) -> Result<Vec<WindowId>> {
+        let snapshot = self
+            .state
+            .history
+            .undo
+            .back()
+            .cloned()
+            .ok_or(Error::NothingToUndo)?;
+
+        let current = self.snapshot();
+        let returning = self.restore(snapshot, open)?;
+        self.state.history.undo.pop_back();
+        self.state.history.redo.push(current);
+
+        LogicalUndo(self.state.focused_window).log(&mut self.logger);
+        Ok(returning)
+    }
+
+    /// Restore the tree and focus from before the last undo, returning the
+    /// windows that come back from elsewhere.
+    pub fn redo(&mut self, open: &HashSet<WindowId>) -> Result<Vec<WindowId>> {
+        let snapshot = self
+            .state
+            .history
+            .redo
+            .last()
+            .cloned()
+            .ok_or(Error::NothingToRedo)?;
+
+        let current = self.snapshot();
+        let returning = self.restore(snapshot, open)?;
+        self.state.history.redo.pop();
+        self.remember(current);
+
+        LogicalRedo(self.state.focused_window).log(&mut self.logger);
+        Ok(returning)
+    }
+
+    /// Replace the tree and focus with those of `snapshot`, returning the
+    /// windows it has that the logical display does not.
+    // The windows may have changed since the snapshot was taken, so before
+    // restoring it:
+    //  - Remove the windows that have since closed, handing focus on if the
+    //    focused window was one of them.
+    //  - Add the windows that have since arrived beside the focused window, so
+    //    that none stop being managed.
+    fn restore(
+        &mut self,
+        mut snapshot: Snapshot,
+        open: &HashSet<WindowId>,
+    ) -> Result<Vec<WindowId>> {
+        let padding = self.config.window_padding();
+        let current = self.window_ids();
+        let restored = snapshot.root.window_ids();
+
+        for &closed in restored.iter().filter(|w| !open.contains(w)) {
+            snapshot.root.remove_window(closed, padding)?;
+        }
+
+        let mut remaining = snapshot.root.window_ids();
+        for window in self.state.root.windows() {
+            if remaining.contains(&window.id) {
+                continue;
+            }
+
+            if remaining.is_empty() {
+                snapshot.root.add_window(window, padding)?;
+            } else {
+                let beside = if remaining.contains(&snapshot.focused_window) {
+                    snapshot.focused_window
+                } else {
+                    snapshot.root.focus_target()
+                };
+                snapshot.root.add_window_beside(beside, window, padding)?;
+            }
+            remaining.insert(window.id);
+        }
+
+        if !remaining.contains(&snapshot.focused_window) {
+            snapshot.focused_window = snapshot.root.focus_target();
+            snapshot.focus_depth = 0;
+        }
+
+        let returning = remaining
+            .iter()
+            .filter(|w| !current.contains(w))
+            .copied()
+            .collect();
+
+        self.state.root = snapshot.root;
+        self.state.fullscreen = None;
+        self.focus(snapshot.focused_window);
+        self.state.focus_depth = snapshot.focus_depth.min(self.root_depth());
+
+        Ok(returning)
+    }
 }
 
 pub enum RemoveResult {
     StillHasWindows(Display<SomeWindows>),
     NowEmpty(Display<NoWindows>),
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bounds() -> Bounds {
+        Bounds {
+            x: 0.0,
+            y: 0.0,
+            width: 1200.0,
+            height: 800.0,
+        }
+    }
+
+    fn window(id: u32) -> container::Window {
+        container::Window {
+            id: WindowId::from(id),
+            min_width: 0.0,
+            min_height: 0.0,
+        }
+    }
+
+    fn display_of(ids: &[u32]) -> Display<SomeWindows> {
+        let config = crate::display::physical::Config {
+            window_padding: Some(10.0),
+            ..Default::default()
+        };
+        let mut display = Display::new(Id(0), bounds(), config.into())
+            .add_window(window(ids[0]))
+            .unwrap();
+        for &id in &ids[1..] {
+            display.add_window(window(id)).unwrap();
+        }
+        display
+    }
+
+    #[test]
+    fn fullscreen_window_covers_display_and_keeps_its_tiled_place() {
+        let mut display = display_of(&[1, 2]);
+        let tiled = display.window_bounds();
+
+        display.set_fullscreen(true);
+
+        let bounds = display.window_bounds();
+        assert_eq!(bounds[&WindowId::from(2u32)], display.state.root.bounds());
+        assert_eq!(bounds[&WindowId::from(1u32)], tiled[&WindowId::from(1u32)]);
+
+        display.set_fullscreen(false);
+        assert_eq!(display.window_bounds(), tiled);
+    }
+
+    #[test]
+    fn focusing_another_window_leaves_fullscreen() {
+        let mut display = display_of(&[1, 2]);
+        display.set_fullscreen(true);
+
+        display.set_focused_window(WindowId::from(1u32)).unwrap();
+
+        assert!(!display.is_fullscreen());
+    }
+
+    #[test]
+    fn closing_fullscreen_window_leaves_fullscreen() {
+        let mut display = display_of(&[1, 2, 3]);
+        display.set_fullscreen(true);
+
+        let RemoveResult::StillHasWindows(display) =
+            display.remove_window(WindowId::from(3u32)).unwrap()
+        else {
+            unreachable!()
+        };
+
+        assert!(!display.is_fullscreen());
+        assert_eq!(display.focused_window(), WindowId::from(2u32));
+    }
+
+    fn open(ids: &[u32]) -> HashSet<WindowId> {
+        ids.iter().copied().map(WindowId::from).collect()
+    }
+
+    fn without(display: Display<SomeWindows>, id: u32) -> Display<SomeWindows> {
+        match display.remove_window(WindowId::from(id)).unwrap() {
+            RemoveResult::StillHasWindows(display) => display,
+            RemoveResult::NowEmpty(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_restore_tree_and_focus() {
+        let mut display = display_of(&[1, 2, 3]);
+        let before = display.window_bounds();
+
+        display.move_focused_window(Direction::Left).unwrap();
+        display.set_focused_window(WindowId::from(1u32)).unwrap();
+        let after = display.window_bounds();
+        assert_ne!(before, after);
+
+        assert!(display.undo(&open(&[1, 2, 3])).unwrap().is_empty());
+        assert_eq!(display.window_bounds(), before);
+        assert_eq!(display.focused_window(), WindowId::from(3u32));
+
+        display.redo(&open(&[1, 2, 3])).unwrap();
+        assert_eq!(display.window_bounds(), after);
+        assert_eq!(display.focused_window(), WindowId::from(1u32));
+    }
+
+    #[test]
+    fn undo_with_empty_history_errors() {
+        let mut display = display_of(&[1, 2]);
+
+        assert!(matches!(
+            display.undo(&open(&[1, 2])),
+            Err(Error::NothingToUndo)
+        ));
+        assert!(matches!(
+            display.redo(&open(&[1, 2])),
+            Err(Error::NothingToRedo)
+        ));
+    }
+
+    #[test]
+    fn new_change_forgets_undone_changes() {
+        let mut display = display_of(&[1, 2]);
+        display.split(Axis::Vertical).unwrap();
+        display.undo(&open(&[1, 2])).unwrap();
+
+        display.balance_all().unwrap();
+
+        assert!(matches!(
+            display.redo(&open(&[1, 2])),
+            Err(Error::NothingToRedo)
+        ));
+    }
+
+    #[test]
+    fn history_forgets_oldest_changes_beyond_limit() {
+        let mut display = display_of(&[1, 2]);
+        for _ in 0..HISTORY_LIMIT + 3 {
+            display.balance_all().unwrap();
+        }
+
+        for _ in 0..HISTORY_LIMIT {
+            display.undo(&open(&[1, 2])).unwrap();
+        }
+        assert!(matches!(
+            display.undo(&open(&[1, 2])),
+            Err(Error::NothingToUndo)
+        ));
+    }
+
+    #[test]
+    fn undo_takes_back_a_window_taken_away() {
+        let mut display = display_of(&[1, 2, 3]);
+        let before = display.window_bounds();
+
+        display.checkpoint();
+        let mut display = without(display, 3);
+
+        let returning = display.undo(&open(&[1, 2, 3])).unwrap();
+
+        assert_eq!(returning, vec![WindowId::from(3u32)]);
+        assert_eq!(display.window_bounds(), before);
+    }
+
+    #[test]
+    fn undo_leaves_out_closed_windows_and_keeps_new_ones() {
+        let mut display = display_of(&[1, 2]);
+        display.split(Axis::Vertical).unwrap();
+        let mut display = without(display, 1);
+        display.add_window(window(3)).unwrap();
+
+        let returning = display.undo(&open(&[2, 3])).unwrap();
+
+        assert!(returning.is_empty());
+        assert_eq!(display.window_ids(), open(&[2, 3]));
+        assert_eq!(display.focused_window(), WindowId::from(2u32));
+    }
+}
@@ -6,19 +6,22 @@ mod tests;
 
 use crate::display::log::Message::{
     AddLogical, AddPhysical, AddedWindow, AddingWindow, ChoseNewLogicalId, CouldNotFitWindow,
-    FocusLogical, NoNewLogicalIds, RemovedEmptyLogical, RemovedWindow, SetActivePhysical, Split,
-    SwitchToLogical,
+    FocusLogical, NoNewLogicalIds, RecordedFocus, RemovedEmptyLogical, RemovedWindow,
+    RestoredWindow, SetActivePhysical, SetLayout, ShiftFocusToPhysical, Split, SwitchToLogical,
 };
 use crate::display::logical::SomeWindows;
 use crate::display::registry::Registry;
 use crate::log::{Level, Log, Prefix};
 use crate::{
-    container::{Axis, Window},
+    container::{
+        Axis, Layout, LayoutChange, TreePosition, Window, furthest_in_direction,
+        nearest_in_direction,
+    },
     error::Error,
     error::Result,
     log::Logger,
 };
-use core_graphics::{Bounds, WindowId};
+use core_graphics::{Bounds, Direction, WindowId};
 use std::collections::{HashMap, HashSet};
 use std::io::Write;
 
@@ -32,6 +35,15 @@ pub struct Displays<S> {
 pub struct Uninitialised;
 pub struct Initialised {
     active_physical_display_id: physical::Id,
+    /// The logical display that was active before the current one, for
+    /// `workspace back_and_forth`.
+    previous_logical_display_id: Option<logical::Id>,
+    /// Every managed window that has held focus, least recently focused first.
+    focus_history: Vec<WindowId>,
+    /// The logical display each floating window belongs to, which hides and
+    /// shows it along with the windows it manages. Sticky windows belong to
+    /// their physical display instead.
+    floating: HashMap<WindowId, logical::Id>,
 }
 
 impl Displays<Uninitialised> {
@@ -68,6 +80,9 @@ impl Displays<Uninitialised> {
             logger: self.logger,
             state: Initialised {
                 active_physical_display_id: pid,
+                previous_logical_display_id: None,
+                focus_history: Vec::new(),
+                floating: HashMap::new(),
             },
         };
 
@@ -84,6 +99,13 @@ impl Displays<Initialised> {
             .map(|(pid, _)| *pid)
     }
 
+    /// Returns the ID of the logical display that manages the provided window.
+    pub fn logical_of_window(&self, wid: WindowId) -> Option<logical::Id> {
+        self.physical_displays
+            .values()
+            .find_map(|pd| pd.logical_of_window(wid))
+    }
+
     /// Returns the ID of the physical display that manages the provided logical
     /// display.
     pub fn logical_id_owner(&self, id: logical::Id) -> Option<physical::Id> {
@@ -109,8 +131,15 @@ impl Displays<Initialised> {
 
         // Safety: we just created it
         let pid = self.registry.owner_of(id).unwrap();
-        self.state.active_physical_display_id = pid;
-        self.physical_displays.get(&pid).unwrap().focused_window()
+        if pid != self.state.active_physical_display_id {
+            self.set_active_physical_display(pid);
+        }
+
+        let focused = self.physical_displays.get(&pid).unwrap().focused_window();
+        if let Some(wid) = focused {
+            self.record_focus(wid);
+        }
+        focused
     }
 
     pub fn split(&mut self, axis: Axis) -> Result<()> {
@@ -123,8 +152,144 @@ impl Displays<Initialised> {
         Ok(())
     }
 
+    pub fn set_layout(&mut self, change: LayoutChange) -> Result<Layout> {
+        let layout = self
+            .physical_displays
+            .get_mut(&self.state.active_physical_display_id)
+            .unwrap()
+            .set_layout(change)?;
+
+        SetLayout(layout).log(&mut self.logger);
+        Ok(layout)
+    }
+
+    /// Shift focus in `direction`, returning the newly focused window.
+    ///
+    /// When the active logical display has no window in `direction`, focus
+    /// moves on to the nearest physical display that way, and when there is
+    /// none and `wrap` is set, to the furthest physical display the other way
+    /// (which may be the active one). `None` is returned if focus stayed put,
+    /// or landed on a physical display with no windows.
+    // To find the physical display to move to, compare screen bounds in the
+    // same way windows are compared within a logical display: the candidate
+    // must lie wholly beyond the active display and overlap it on the
+    // perpendicular axis.
+    pub fn shift_focus(&mut self, direction: Direction, wrap: bool) -> Result<Option<WindowId>> {
+        let pid = self.state.active_physical_display_id;
+        let active = self.physical_displays.get_mut(&pid).unwrap();
+
+        match active.shift_focus(direction) {
+            Ok(Some(window)) => {
+                self.record_focus(window);
+                return Ok(Some(window));
+            }
+            Ok(None) | Err(Error::CannotFocusEmptyDisplay) => {}
+            Err(e) => return Err(e),
+        }
+
+        let from = active.bounds();
+        let others: Vec<_> = self
+            .physical_displays
+            .iter()
+            .filter(|(id, _)| **id != pid)
+            .map(|(id, pd)| (*id, pd.bounds()))
+            .collect();
+
+        let target = match nearest_in_direction(from, others.iter().copied(), direction) {
+            Some(target) => target,
+            None if wrap => furthest_in_direction(from, others, opposite(direction)).unwrap_or(pid),
+            None => return Ok(None),
+        };
+
+        if target == pid {
+            return match self
+                .active_physical_display_mut()
+                .focus_edge(opposite(direction))
+            {
+                Ok(window) => {
+                    self.record_focus(window);
+                    Ok(Some(window))
+                }
+                Err(Error::CannotFocusEmptyDisplay) => Ok(None),
+                Err(e) => Err(e),
+            };
+        }
+
+        self.set_active_physical_display(target);
+        ShiftFocusToPhysical(direction, target).log(&mut self.logger);
+
+        let focused = self.physical_displays[&target].focused_window();
+        if let Some(wid) = focused {
+            self.record_focus(wid);
+        }
+        Ok(focused)
+    }
+
+    /// Returns the logical display that was active before the current one.
+    pub fn previous_logical_display_id(&self) -> Option<logical::Id> {
+        self.state.previous_logical_display_id
+    }
+
+    /// Note that `wid` has taken focus: make the physical display managing it
+    /// active, and move it to the top of the focus history. Windows the
+    /// displays do not manage, such as floating ones, are not recorded.
+    pub fn window_focused(&mut self, wid: WindowId) {
+        let Some(pid) = self.display_of_window(wid) else {
+            return;
+        };
+
+        if pid != self.state.active_physical_display_id {
+            self.set_active_physical_display(pid);
+        }
+        self.record_focus(wid);
+    }
+
+    fn record_focus(&mut self, wid: WindowId) {
+        let history = &mut self.state.focus_history;
+        if history.last() != Some(&wid) {
+            history.retain(|&w| w != wid);
+            history.push(wid);
+            RecordedFocus(wid).log(&mut self.logger);
+        }
+    }
+
+    /// Forget a window that has closed, so it is never returned to.
+    pub fn forget_window(&mut self, wid: WindowId) {
+        self.state.focus_history.retain(|&w| w != wid);
+    }
+
+    /// Returns the most recently focused window other than `current` that is
+    /// still managed, on any logical or physical display.
+    pub fn last_focused_window(&self, current: Option<WindowId>) -> Option<WindowId> {
+        self.state
+            .focus_history
+            .iter()
+            .rev()
+            .copied()
+            .filter(|&w| Some(w) != current)
+            .find(|&w| self.display_of_window(w).is_some())
+    }
+
+    /// Returns whichever of `candidates` was focused most recently, or the
+    /// lowest of them when none has held focus.
+    pub fn most_recently_focused(&self, candidates: &[WindowId]) -> Option<WindowId> {
+        self.state
+            .focus_history
+            .iter()
+            .rev()
+            .copied()
+            .find(|w| candidates.contains(w))
+            .or_else(|| candidates.iter().copied().min())
+    }
+
+    /// Make the physical display `id` active, remembering the logical display
+    /// shown on the one active before it for `workspace back_and_forth`.
     pub fn set_active_physical_display(&mut self, id: physical::Id) {
+        let active_lid = self.active_logical_display_id();
         self.state.active_physical_display_id = id;
+        if self.active_logical_display_id() != active_lid {
+            self.state.previous_logical_display_id = Some(active_lid);
+        }
         SetActivePhysical(id).log(&mut self.logger);
     }
 
@@ -135,6 +300,11 @@ impl Displays<Initialised> {
     pub fn switch_logical_display(&mut self, pid: physical::Id, new_lid: logical::Id) {
         SwitchToLogical(pid, new_lid).log(&mut self.logger);
 
+        let active_lid = self.active_logical_display_id();
+        if active_lid != new_lid {
+            self.state.previous_logical_display_id = Some(active_lid);
+        }
+
         let pd = self.physical_displays.get_mut(&pid).unwrap();
         let old_lid = pd.active_logical_id();
         pd.switch_to(new_lid);
@@ -158,7 +328,7 @@ impl Displays<Initialised> {
         self.physical_displays.insert(pid, pd);
         self.registry.register(lid, pid);
 
-        self.state.active_physical_display_id = pid;
+        self.set_active_physical_display(pid);
         AddPhysical(pid, lid).log(&mut self.logger);
     }
 
@@ -280,6 +450,172 @@ impl Displays<Initialised> {
         Ok(())
     }
 
+    /// Returns the ID of the physical display that `wid` is sticky on.
+    pub fn sticky_owner(&self, wid: WindowId) -> Option<physical::Id> {
+        self.physical_displays
+            .iter()
+            .find(|(_, pd)| pd.is_sticky(wid))
+            .map(|(pid, _)| *pid)
+    }
+
+    /// Returns the ID of the physical display that `bounds` are centred on, or
+    /// the active one when they are off screen.
+    pub fn display_under(&self, bounds: Bounds) -> physical::Id {
+        let (x, y) = (
+            bounds.x + bounds.width / 2.0,
+            bounds.y + bounds.height / 2.0,
+        );
+        self.physical_displays
+            .iter()
+            .find(|(_, pd)| {
+                let area = pd.bounds();
+                (area.x..area.x + area.width).contains(&x)
+                    && (area.y..area.y + area.height).contains(&y)
+            })
+            .map(|(pid, _)| *pid)
+            .unwrap_or(self.state.active_physical_display_id)
+    }
+
+    /// Returns the ID of the logical display shown on the physical display that
+    /// `bounds` are centred on, or on the active one when they are off screen.
+    pub fn logical_under(&self, bounds: Bounds) -> logical::Id {
+        self.physical_displays[&self.display_under(bounds)].active_logical_id()
+    }
+
+    /// Returns the ID of the logical display that the floating window `wid`
+    /// belongs to.
+    pub fn floating_owner(&self, wid: WindowId) -> Option<logical::Id> {
+        self.state.floating.get(&wid).copied()
+    }
+
+    /// Make the floating window `wid` belong to the logical display `lid`.
+    pub fn add_floating(&mut self, wid: WindowId, lid: logical::Id) {
+        self.state.floating.insert(wid, lid);
+    }
+
+    /// Stop tracking `wid` as a floating window, whether it belongs to a
+    /// logical display or is sticky on a physical one.
+    pub fn remove_floating(&mut self, wid: WindowId) {
+        self.state.floating.remove(&wid);
+        for pd in self.physical_displays.values_mut() {
+            pd.set_sticky(wid, false);
+        }
+    }
+
+    /// Make the floating window `wid` sticky on the physical display its
+    /// `bounds` are centred on, or the active one when they are off screen.
+    pub fn stick(&mut self, wid: WindowId, bounds: Bounds) {
+        let pid = self.display_under(bounds);
+        self.state.floating.remove(&wid);
+        self.physical_displays
+            .get_mut(&pid)
+            .unwrap()
+            .set_sticky(wid, true);
+    }
+
+    /// Stop `wid` from being sticky, so that it belongs to the logical display
+    /// shown on the physical display it was sticky on.
+    pub fn unstick(&mut self, wid: WindowId) {
+        let Some(pid) = self.sticky_owner(wid) else {
+            return;
+        };
+        let pd = self.physical_displays.get_mut(&pid).unwrap();
+        pd.set_sticky(wid, false);
+        self.state.floating.insert(wid, pd.active_logical_id());
+    }
+
+    /// Returns the windows hidden and shown along with the logical display
+    /// `lid`: those it manages, and the floating windows that belong to it.
+    pub fn windows_shown_with(&self, lid: logical::Id) -> HashSet<WindowId> {
+        let mut windows = self
+            .get_occupied_logical(lid)
+            .map(|ld| ld.window_ids())
+            .unwrap_or_default();
+        windows.extend(
+            self.state
+                .floating
+                .iter()
+                .filter(|&(_, &owner)| owner == lid)
+                .map(|(&wid, _)| wid),
+        );
+        windows
+    }
+
+    /// Remember the tree of the logical display managing `wid`, so that taking
+    /// the window away from it can be undone.
+    pub fn checkpoint_window(&mut self, wid: WindowId) {
+        if let Some(pid) = self.display_of_window(wid) {
+            self.physical_displays
+                .get_mut(&pid)
+                .unwrap()
+                .checkpoint_window(wid);
+        }
+    }
+
+    /// Remember the tree of the logical display `lid`, so that bringing a
+    /// window to it can be undone.
+    pub fn checkpoint_logical(&mut self, lid: logical::Id) {
+        if let Some(pid) = self.registry.owner_of(lid) {
+            self.physical_displays
+                .get_mut(&pid)
+                .unwrap()
+                .checkpoint_logical(lid);
+        }
+    }
+
+    /// Return where `wid` sits in the tree of the logical display managing it,
+    /// so that it can be put back there after being taken away.
+    pub fn position_of_window(&self, wid: WindowId) -> Option<TreePosition> {
+        let pid = self.display_of_window(wid)?;
+        self.physical_displays.get(&pid)?.position_of_window(wid)
+    }
+
+    /// Put a window back where `position` says it sat, if its old place is on
+    /// the active logical display. Errors otherwise, or if it no longer fits,
+    /// leaving the caller to add the window as a new one instead.
+    pub fn restore_window(&mut self, position: &TreePosition) -> Result<()> {
+        let lid = self.active_logical_display_id();
+        self.active_physical_display_mut()
+            .restore_window(position)?;
+
+        RestoredWindow(position.window.id, lid).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Undo the last tree-changing operation on the active logical display,
+    /// returning the windows it takes back from other logical displays or from
+    /// outside the tree. `open` holds every window that still exists.
+    pub fn undo(&mut self, open: &HashSet<WindowId>) -> Result<Vec<WindowId>> {
+        let returning = self.active_physical_display_mut().undo(open)?;
+        self.release_to_active(&returning)?;
+        Ok(returning)
+    }
+
+    /// Redo the last undone operation on the active logical display, returning
+    /// the windows it takes back as `undo` does.
+    pub fn redo(&mut self, open: &HashSet<WindowId>) -> Result<Vec<WindowId>> {
+        let returning = self.active_physical_display_mut().redo(open)?;
+        self.release_to_active(&returning)?;
+        Ok(returning)
+    }
+
+    /// Take `windows` out of any physical display other than the active one.
+    fn release_to_active(&mut self, windows: &[WindowId]) -> Result<()> {
+        let active = self.state.active_physical_display_id;
+        for (&pid, pd) in self.physical_displays.iter_mut() {
+            if pid == active {
+                continue;
+            }
+            for &wid in windows {
+                if pd.window_ids().contains(&wid) {
+                    pd.remove_window(wid)?;
+                    RemovedWindow(wid, pid).log(&mut self.logger);
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn logical_ids(&self, pid: physical::Id) -> HashSet<logical::Id> {
         // TODO: return Iterator<Item = logical::Id> here too?
         self.registry.logicals(pid).collect()
@@ -292,6 +628,15 @@ impl Displays<Initialised> {
     }
 }
 
+fn opposite(direction: Direction) -> Direction {
+    match direction {
+        Direction::Left => Direction::Right,
+        Direction::Right => Direction::Left,
+        Direction::Up => Direction::Down,
+        Direction::Down => Direction::Up,
+    }
+}
+
 pub enum AddWindowResult {
     Active(logical::Id),
     Overflow(logical::Id),
@@ -350,4 +695,184 @@ mod test {
         assert_eq!(d.active_logical_display_id(), *lids.iter().next().unwrap());
         assert_eq!(d.active_logical_display_id(), lid(0));
     }
+
+    fn displays_with_windows(ids: &[u32]) -> Displays<Initialised> {
+        let area = Bounds {
+            width: 1200.0,
+            height: 800.0,
+            ..bounds()
+        };
+        let mut d = Displays::default()
+            .add_first_physical(pid(0), area, Default::default())
+            .unwrap();
+        for &id in ids {
+            d.add_window(Window {
+                id: WindowId::from(id),
+                min_width: 0.0,
+                min_height: 0.0,
+            })
+            .unwrap();
+        }
+        d
+    }
+
+    #[test]
+    fn last_focused_window_skips_the_current_window() {
+        let mut d = displays_with_windows(&[1, 2, 3]);
+        for id in [1u32, 2, 3, 2] {
+            d.window_focused(WindowId::from(id));
+        }
+
+        let current = Some(WindowId::from(2u32));
+        assert_eq!(d.last_focused_window(current), Some(WindowId::from(3u32)));
+
+        d.window_focused(WindowId::from(1u32));
+        assert_eq!(
+            d.last_focused_window(Some(WindowId::from(1u32))),
+            Some(WindowId::from(2u32))
+        );
+    }
+
+    #[test]
+    fn last_focused_window_ignores_forgotten_and_unmanaged_windows() {
+        let mut d = displays_with_windows(&[1, 2]);
+        for id in [1u32, 2, 7] {
+            d.window_focused(WindowId::from(id));
+        }
+
+        assert_eq!(d.last_focused_window(None), Some(WindowId::from(2u32)));
+
+        d.forget_window(WindowId::from(2u32));
+        assert_eq!(d.last_focused_window(None), Some(WindowId::from(1u32)));
+        assert_eq!(d.last_focused_window(Some(WindowId::from(1u32))), None);
+    }
+
+    #[test]
+    fn changing_physical_display_remembers_the_previous_logical_display() {
+        let mut d = displays_with_windows(&[1]);
+        let area = Bounds {
+            x: 1200.0,
+            ..bounds()
+        };
+        d.add_physical(pid(1), area, Default::default());
+        d.set_active_physical_display(pid(0));
+        assert_eq!(d.active_logical_display_id(), lid(0));
+
+        d.set_active_physical_display(pid(1));
+        assert_eq!(d.previous_logical_display_id(), Some(lid(0)));
+
+        d.set_active_physical_display(pid(0));
+        assert_eq!(d.previous_logical_display_id(), Some(lid(1)));
+    }
+
+    #[test]
+    fn most_recently_focused_prefers_the_focus_history() {
+        let mut d = displays_with_windows(&[1, 2, 3]);
+        for id in [3u32, 1, 2] {
+            d.window_focused(WindowId::from(id));
+        }
+
+        let candidates = [WindowId::from(3u32), WindowId::from(1u32)];
+        assert_eq!(
+            d.most_recently_focused(&candidates),
+            Some(WindowId::from(1u32))
+        );
+    }
+
+    #[test]
+    fn most_recently_focused_falls_back_to_the_lowest_window() {
+        let d = displays_with_windows(&[1]);
+
+        let candidates = [WindowId::from(9u32), WindowId::from(4u32)];
+        assert_eq!(
+            d.most_recently_focused(&candidates),
+            Some(WindowId::from(4u32))
+        );
+        assert_eq!(d.most_recently_focused(&[]), None);
+    }
+
+    #[test]
+    fn switching_logical_display_remembers_the_previous_one() {
+        let mut d = displays_with_windows(&[1]);
+        assert_eq!(d.previous_logical_display_id(), None);
+
+        d.create_logical_display(pid(0), lid(1)).unwrap();
+        d.switch_logical_display(pid(0), lid(1));
+        assert_eq!(d.previous_logical_display_id(), Some(lid(0)));
+
+        d.switch_logical_display(pid(0), lid(0));
+        assert_eq!(d.previous_logical_display_id(), Some(lid(1)));
+    }
+
+    fn wid(id: u32) -> WindowId {
+        WindowId::from(id)
+    }
+
+    #[test]
+    fn stick_moves_a_floating_window_to_its_physical_display() {
+        let mut d = displays_with_windows(&[1]);
+        d.add_floating(wid(5), lid(0));
+        assert_eq!(d.sticky_owner(wid(5)), None);
+
+        d.stick(wid(5), bounds());
+        assert_eq!(d.sticky_owner(wid(5)), Some(pid(0)));
+        assert_eq!(d.floating_owner(wid(5)), None);
+    }
+
+    #[test]
+    fn unstick_gives_the_window_to_the_shown_logical_display() {
+        let mut d = displays_with_windows(&[1]);
+        d.add_floating(wid(5), lid(0));
+        d.stick(wid(5), bounds());
+
+        d.create_logical_display(pid(0), lid(1)).unwrap();
+        d.switch_logical_display(pid(0), lid(1));
+        d.unstick(wid(5));
+
+        assert_eq!(d.sticky_owner(wid(5)), None);
+        assert_eq!(d.floating_owner(wid(5)), Some(lid(1)));
+    }
+
+    #[test]
+    fn unstick_leaves_a_window_that_is_not_sticky_alone() {
+        let mut d = displays_with_windows(&[1]);
+        d.add_floating(wid(5), lid(0));
+
+        d.unstick(wid(5));
+        d.unstick(wid(6));
+
+        assert_eq!(d.floating_owner(wid(5)), Some(lid(0)));
+        assert_eq!(d.floating_owner(wid(6)), None);
+    }
+
+    #[test]
+    fn remove_floating_forgets_sticky_and_owned_windows() {
+        let mut d = displays_with_windows(&[1]);
+        d.add_floating(wid(5), lid(0));
+        d.add_floating(wid(6), lid(0));
+        d.stick(wid(6), bounds());
+
+        d.remove_floating(wid(5));
+        d.remove_floating(wid(6));
+
+        assert_eq!(d.floating_owner(wid(5)), None);
+        assert_eq!(d.sticky_owner(wid(6)), None);
+        assert_eq!(d.floating_owner(wid(6)), None);
+    }
+
+    #[test]
+    fn switching_logical_display_hides_floating_windows_but_not_sticky_ones() {
+        let mut d = displays_with_windows(&[1, 2]);
+        d.add_floating(wid(5), lid(0));
+        d.add_floating(wid(6), lid(0));
+        d.stick(wid(6), bounds());
+        d.create_logical_display(pid(0), lid(1)).unwrap();
+        d.add_floating(wid(7), lid(1));
+
+        let hidden = d.windows_shown_with(lid(0));
+        assert_eq!(hidden, HashSet::from([wid(1), wid(2), wid(5)]));
+
+        let shown = d.windows_shown_with(lid(1));
+        assert_eq!(shown, HashSet::from([wid(7)]));
+    }
 }
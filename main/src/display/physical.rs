@@ -1,13 +1,16 @@
 use crate::display::logical::{NoWindows, SomeWindows};
 use crate::log::Prefix;
 use crate::{
-    container::{Axis, Window},
+    container::{Axis, Layout, LayoutChange, Resize, TitleStrip, TreePosition, Window},
     display::{
         log::Message::{
-            PhysicalAddedLogical, PhysicalAddedWindow, PhysicalAddedWindowToLogical, PhysicalNew,
-            PhysicalRemovedLogical, PhysicalRemovedWindow, PhysicalResizeFocused,
-            PhysicalSetFocused, PhysicalShiftFocus, PhysicalSplit, PhysicalSwitchActive,
-            PhysicalSwitchDisplay,
+            PhysicalAddedLogical, PhysicalAddedWindow, PhysicalAddedWindowToLogical,
+            PhysicalBalance, PhysicalBalanceAll, PhysicalFocusChild, PhysicalFocusEdge,
+            PhysicalFocusParent, PhysicalMoveFocused, PhysicalNew, PhysicalRedo,
+            PhysicalRemovedLogical, PhysicalRemovedWindow, PhysicalResize, PhysicalResizeFocused,
+            PhysicalRestoredWindow, PhysicalSetFocused, PhysicalSetFullscreen, PhysicalSetLayout,
+            PhysicalSetSticky, PhysicalShiftFocus, PhysicalSplit, PhysicalSwitchActive,
+            PhysicalSwitchDisplay, PhysicalUndo,
         },
         logical,
     },
@@ -50,13 +53,17 @@ impl Id {
 pub struct Config {
     pub window_padding: Option<f64>,
     pub log_level: Level,
+    pub resize_amount: Option<f64>,
+    pub min_window_size: Option<f64>,
 }
 
-impl From<crate::config::Config> for Config {
-    fn from(value: crate::config::Config) -> Self {
+impl From<&crate::config::Config> for Config {
+    fn from(value: &crate::config::Config) -> Self {
         Self {
             window_padding: value.window_padding,
             log_level: value.log_level,
+            resize_amount: value.resize_amount,
+            min_window_size: value.min_window_size,
         }
     }
 }
@@ -110,6 +117,13 @@ impl LogicalDisplays {
         self.empty.keys().chain(self.occupied.keys()).copied()
     }
 
+    pub fn logical_of_window(&self, window_id: WindowId) -> Option<logical::Id> {
+        self.occupied
+            .iter()
+            .find(|(_, ld)| ld.window_ids().contains(&window_id))
+            .map(|(id, _)| *id)
+    }
+
     pub fn window_ids(&self) -> HashSet<WindowId> {
         self.occupied
             .values()
@@ -141,16 +155,32 @@ impl LogicalDisplays {
         Ok(())
     }
 
+    /// Put a window back where `position` says it sat in the active logical
+    /// display's tree.
+    pub fn restore_window(&mut self, position: &TreePosition) -> Result<()> {
+        self.occupied
+            .get_mut(&self.active)
+            .ok_or(Error::CannotFindWindow)?
+            .restore_window(position)
+    }
+
+    /// Return where `window_id` sits in the tree of the logical display
+    /// managing it.
+    pub fn position_of_window(&self, window_id: WindowId) -> Option<TreePosition> {
+        self.occupied
+            .values()
+            .find_map(|ld| ld.position_of_window(window_id))
+    }
+
     pub fn remove_window(&mut self, window_id: WindowId) -> Result<()> {
         let lid = self
-            .occupied
-            .iter()
-            .find(|(_, ld)| ld.window_ids().contains(&window_id))
-            .map(|(lid, _)| *lid)
+            .logical_of_window(window_id)
             .ok_or(Error::WindowNotFound)?;
+        self.remove_window_from(lid, window_id)
+    }
 
-        // Safety: we just confirmed it is in the set of logical displays
-        let occupied = self.occupied.remove(&lid).unwrap();
+    fn remove_window_from(&mut self, lid: logical::Id, window_id: WindowId) -> Result<()> {
+        let occupied = self.occupied.remove(&lid).ok_or(Error::WindowNotFound)?;
 
         match occupied.remove_window(window_id)? {
             logical::RemoveResult::NowEmpty(display) => {
@@ -195,13 +225,41 @@ impl LogicalDisplays {
             .split(axis)
     }
 
-    pub fn shift_focus(&mut self, direction: Direction) -> Result<WindowId> {
+    pub fn set_layout(&mut self, change: LayoutChange) -> Result<Layout> {
+        self.occupied
+            .get_mut(&self.active)
+            .ok_or(Error::CannotFocusEmptyDisplay)?
+            .set_layout(change)
+    }
+
+    pub fn balance(&mut self) -> Result<()> {
+        self.occupied
+            .get_mut(&self.active)
+            .ok_or(Error::CannotFocusEmptyDisplay)?
+            .balance()
+    }
+
+    pub fn balance_all(&mut self) -> Result<()> {
+        self.occupied
+            .get_mut(&self.active)
+            .ok_or(Error::CannotFocusEmptyDisplay)?
+            .balance_all()
+    }
+
+    pub fn shift_focus(&mut self, direction: Direction) -> Result<Option<WindowId>> {
         self.occupied
             .get_mut(&self.active)
             .ok_or(Error::CannotFocusEmptyDisplay)?
             .shift_focus(direction)
     }
 
+    pub fn focus_edge(&mut self, direction: Direction) -> Result<WindowId> {
+        self.occupied
+            .get_mut(&self.active)
+            .ok_or(Error::CannotFocusEmptyDisplay)
+            .map(|ld| ld.focus_edge(direction))
+    }
+
     pub fn set_focused_window(&mut self, window_id: WindowId) -> Result<()> {
         self.occupied
             .values_mut()
@@ -216,6 +274,34 @@ impl LogicalDisplays {
             .map(|ld| ld.focused_window())
     }
 
+    pub fn focus_parent(&mut self) -> Result<()> {
+        self.occupied
+            .get_mut(&self.active)
+            .ok_or(Error::CannotFocusEmptyDisplay)?
+            .focus_parent()
+    }
+
+    pub fn focus_child(&mut self) -> Result<()> {
+        self.occupied
+            .get_mut(&self.active)
+            .ok_or(Error::CannotFocusEmptyDisplay)?
+            .focus_child()
+    }
+
+    pub fn selected_windows(&self) -> Vec<Window> {
+        self.occupied
+            .get(&self.active)
+            .map(|d| d.selected_windows())
+            .unwrap_or_default()
+    }
+
+    pub fn move_focused_window(&mut self, direction: Direction) -> Result<()> {
+        self.occupied
+            .get_mut(&self.active)
+            .ok_or(Error::CannotFocusEmptyDisplay)?
+            .move_focused_window(direction)
+    }
+
     pub fn resize_focused_window(&mut self, direction: Direction) -> Result<()> {
         self.occupied
             .get_mut(&self.active)
@@ -223,8 +309,93 @@ impl LogicalDisplays {
             .resize_focused_window(direction)
     }
 
+    pub fn resize_focused(&mut self, resize: Resize) -> Result<()> {
+        self.occupied
+            .get_mut(&self.active)
+            .ok_or(Error::CannotFocusEmptyDisplay)?
+            .resize_focused(resize)
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.occupied
+            .get(&self.active)
+            .is_some_and(|ld| ld.is_fullscreen())
+    }
+
+    pub fn set_fullscreen(&mut self, fullscreen: bool) -> Result<()> {
+        self.occupied
+            .get_mut(&self.active)
+            .ok_or(Error::CannotFocusEmptyDisplay)?
+            .set_fullscreen(fullscreen);
+        Ok(())
+    }
+
+    /// Remember the tree of the logical display managing `window_id`, before
+    /// the window is taken away from it.
+    pub fn checkpoint_window(&mut self, window_id: WindowId) {
+        if let Some(ld) = self
+            .occupied
+            .values_mut()
+            .find(|ld| ld.window_ids().contains(&window_id))
+        {
+            ld.checkpoint();
+        }
+    }
+
+    /// Remember the tree of the logical display `id`, if it has one, before a
+    /// window is brought to it.
+    pub fn checkpoint(&mut self, id: logical::Id) {
+        if let Some(ld) = self.occupied.get_mut(&id) {
+            ld.checkpoint();
+        }
+    }
+
+    pub fn undo(&mut self, open: &HashSet<WindowId>) -> Result<Vec<WindowId>> {
+        let returning = self
+            .occupied
+            .get_mut(&self.active)
+            .ok_or(Error::NothingToUndo)?
+            .undo(open)?;
+        self.release_to_active(&returning)?;
+        Ok(returning)
+    }
+
+    pub fn redo(&mut self, open: &HashSet<WindowId>) -> Result<Vec<WindowId>> {
+        let returning = self
+            .occupied
+            .get_mut(&self.active)
+            .ok_or(Error::NothingToRedo)?
+            .redo(open)?;
+        self.release_to_active(&returning)?;
+        Ok(returning)
+    }
+
+    /// Take `windows`, which the active logical display has just taken back,
+    /// out of whichever other logical display still manages them.
+    fn release_to_active(&mut self, windows: &[WindowId]) -> Result<()> {
+        for &window_id in windows {
+            let other = self
+                .occupied
+                .iter()
+                .find(|(lid, ld)| **lid != self.active && ld.window_ids().contains(&window_id))
+                .map(|(lid, _)| *lid);
+
+            if let Some(lid) = other {
+                self.remove_window_from(lid, window_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Switching away from a logical display returns any fullscreen window on
+    // it to its tiled bounds.
     pub fn switch_to(&mut self, id: logical::Id) {
         if id != self.active {
+            if let Some(ld) = self.occupied.get_mut(&self.active)
+                && ld.is_fullscreen()
+            {
+                ld.set_fullscreen(false);
+            }
             self.active = id;
         }
     }
@@ -233,6 +404,9 @@ impl LogicalDisplays {
 pub(crate) struct Display {
     bounds: Bounds,
     logicals: LogicalDisplays,
+    /// Floating windows that stay visible whichever logical display is shown,
+    /// rather than belonging to any one of them.
+    sticky: HashSet<WindowId>,
     config: Config,
     logger: Logger,
 }
@@ -248,11 +422,23 @@ impl Display {
         Self {
             bounds,
             logicals: LogicalDisplays::new(logical_id, logical_display),
+            sticky: HashSet::new(),
             config,
             logger,
         }
     }
 
+    /// Returns the screen area the physical display covers.
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    /// The part of the display that windows may occupy, below the menu bar and
+    /// above the status bar.
+    pub fn usable_bounds(&self) -> Bounds {
+        logical::usable_bounds(self.bounds)
+    }
+
     pub fn set_focused_window(&mut self, window_id: WindowId) -> Result<()> {
         self.logicals.set_focused_window(window_id)
     }
@@ -265,6 +451,15 @@ impl Display {
         self.logicals.window_bounds()
     }
 
+    /// Returns the title strips of the active logical display; those of the
+    /// other logical displays are hidden along with their windows.
+    pub fn title_strips(&self) -> Vec<TitleStrip> {
+        self.logicals
+            .get_occupied(self.logicals.active)
+            .map(|d| d.title_strips())
+            .unwrap_or_default()
+    }
+
     // When adding a window to a physical display, delegate to the currently
     // active logical display.
     //
@@ -280,6 +475,11 @@ impl Display {
         self.logicals.active
     }
 
+    /// Returns the ID of the logical display that manages the provided window.
+    pub fn logical_of_window(&self, window_id: WindowId) -> Option<logical::Id> {
+        self.logicals.logical_of_window(window_id)
+    }
+
     pub fn logical_is_empty(&self, id: logical::Id) -> bool {
         self.logicals.is_empty(id)
     }
@@ -302,6 +502,24 @@ impl Display {
         Ok(())
     }
 
+    pub fn set_layout(&mut self, change: LayoutChange) -> Result<Layout> {
+        let layout = self.logicals.set_layout(change)?;
+        PhysicalSetLayout(layout).log(&mut self.logger);
+        Ok(layout)
+    }
+
+    pub fn balance(&mut self) -> Result<()> {
+        self.logicals.balance()?;
+        PhysicalBalance.log(&mut self.logger);
+        Ok(())
+    }
+
+    pub fn balance_all(&mut self) -> Result<()> {
+        self.logicals.balance_all()?;
+        PhysicalBalanceAll.log(&mut self.logger);
+        Ok(())
+    }
+
     pub fn has_logical_display(&self, id: logical::Id) -> bool {
         self.logicals.contains(id)
     }
@@ -324,21 +542,122 @@ impl Display {
         Ok(())
     }
 
+    pub fn focus_parent(&mut self) -> Result<()> {
+        self.logicals.focus_parent()?;
+        PhysicalFocusParent.log(&mut self.logger);
+        Ok(())
+    }
+
+    pub fn focus_child(&mut self) -> Result<()> {
+        self.logicals.focus_child()?;
+        PhysicalFocusChild.log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Returns every window in the active logical display's selected
+    /// container, in tree order.
+    pub fn selected_windows(&self) -> Vec<Window> {
+        self.logicals.selected_windows()
+    }
+
+    pub fn move_focused_window(&mut self, direction: Direction) -> Result<()> {
+        self.logicals.move_focused_window(direction)?;
+        PhysicalMoveFocused(direction).log(&mut self.logger);
+        Ok(())
+    }
+
     pub fn resize_focused_window(&mut self, direction: Direction) -> Result<()> {
         self.logicals.resize_focused_window(direction)?;
         PhysicalResizeFocused(direction).log(&mut self.logger);
         Ok(())
     }
 
+    pub fn resize_focused(&mut self, resize: Resize) -> Result<()> {
+        self.logicals.resize_focused(resize)?;
+        PhysicalResize(resize).log(&mut self.logger);
+        Ok(())
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.logicals.is_fullscreen()
+    }
+
+    pub fn set_fullscreen(&mut self, fullscreen: bool) -> Result<()> {
+        self.logicals.set_fullscreen(fullscreen)?;
+        PhysicalSetFullscreen(fullscreen).log(&mut self.logger);
+        Ok(())
+    }
+
+    pub fn checkpoint_window(&mut self, window_id: WindowId) {
+        self.logicals.checkpoint_window(window_id);
+    }
+
+    pub fn checkpoint_logical(&mut self, id: logical::Id) {
+        self.logicals.checkpoint(id);
+    }
+
+    pub fn position_of_window(&self, window_id: WindowId) -> Option<TreePosition> {
+        self.logicals.position_of_window(window_id)
+    }
+
+    pub fn restore_window(&mut self, position: &TreePosition) -> Result<()> {
+        self.logicals.restore_window(position)?;
+        PhysicalRestoredWindow(position.window.id).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Undo the last tree-changing operation on the active logical display,
+    /// returning the windows it takes back from elsewhere.
+    pub fn undo(&mut self, open: &HashSet<WindowId>) -> Result<Vec<WindowId>> {
+        let returning = self.logicals.undo(open)?;
+        PhysicalUndo.log(&mut self.logger);
+        Ok(returning)
+    }
+
+    /// Redo the last undone operation on the active logical display, returning
+    /// the windows it takes back from elsewhere.
+    pub fn redo(&mut self, open: &HashSet<WindowId>) -> Result<Vec<WindowId>> {
+        let returning = self.logicals.redo(open)?;
+        PhysicalRedo.log(&mut self.logger);
+        Ok(returning)
+    }
+
+    pub fn is_sticky(&self, window_id: WindowId) -> bool {
+        self.sticky.contains(&window_id)
+    }
+
+    /// Make a floating window stay visible on every logical display of the
+    /// physical display, or stop it from doing so.
+    pub fn set_sticky(&mut self, window_id: WindowId, sticky: bool) {
+        let changed = if sticky {
+            self.sticky.insert(window_id)
+        } else {
+            self.sticky.remove(&window_id)
+        };
+
+        if changed {
+            PhysicalSetSticky(window_id, sticky).log(&mut self.logger);
+        }
+    }
+
     pub fn switch_to(&mut self, id: logical::Id) {
         self.logicals.switch_to(id);
         PhysicalSwitchDisplay(id).log(&mut self.logger);
     }
 
     // Delegate focus shifting to the currently active logical display.
-    pub fn shift_focus(&mut self, direction: Direction) -> Result<WindowId> {
+    pub fn shift_focus(&mut self, direction: Direction) -> Result<Option<WindowId>> {
         let window = self.logicals.shift_focus(direction)?;
-        PhysicalShiftFocus(direction, window).log(&mut self.logger);
+        if let Some(window) = window {
+            PhysicalShiftFocus(direction, window).log(&mut self.logger);
+        }
+        Ok(window)
+    }
+
+    /// Focus the window at the `direction` edge of the active logical display.
+    pub fn focus_edge(&mut self, direction: Direction) -> Result<WindowId> {
+        let window = self.logicals.focus_edge(direction)?;
+        PhysicalFocusEdge(direction, window).log(&mut self.logger);
         Ok(window)
     }
 
@@ -346,3 +665,33 @@ impl Display {
         self.logicals.focused_window()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn display() -> Display {
+        let bounds = Bounds {
+            x: 0.0,
+            y: 0.0,
+            width: 1200.0,
+            height: 800.0,
+        };
+        Display::new(Id(0), logical::Id(0), bounds, Default::default())
+    }
+
+    #[test]
+    fn set_sticky_adds_and_removes_the_window() {
+        let mut d = display();
+        let wid = WindowId::from(5u32);
+        assert!(!d.is_sticky(wid));
+
+        d.set_sticky(wid, true);
+        d.set_sticky(wid, true);
+        assert!(d.is_sticky(wid));
+
+        d.set_sticky(wid, false);
+        assert!(!d.is_sticky(wid));
+        assert!(!d.is_sticky(WindowId::from(6u32)));
+    }
+}
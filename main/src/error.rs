@@ -14,6 +14,10 @@ pub enum Error {
     CannotSplitAlreadySplitContainer,
     CannotFocusEmptyDisplay,
     CannotResizeRoot,
+    CannotResizeInDirection,
+    CannotFindSelection,
+    CannotFocusParentOfRoot,
+    CannotFocusChildOfWindow,
     CannotFindParentLeaf,
     ExpectedSplitContainer,
     CouldNotRemoveWindow,
@@ -26,6 +30,13 @@ pub enum Error {
     CannotRemoveOccupiedLogical,
     CannotSplitEmptyLogical,
     CannotFindWindow,
+    EmptyScratchpad,
+    MarkNotFound(String),
+    NoMatchingWindow,
+    NothingToUndo,
+    NothingToRedo,
+    CannotMakeTiledWindowSticky,
+    CannotPositionTiledWindow,
 }
 
 pub(crate) type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,310 @@
+use crate::ctl::{Dimension, PositionArg, ResizeAmount, ResizeArg};
+use core_graphics::{Bounds, Direction};
+
+/// Returns `bounds` moved `px` pixels in `direction`, kept within `area`.
+pub fn moved(bounds: Bounds, direction: Direction, px: f64, area: Bounds) -> Bounds {
+    let (dx, dy) = match direction {
+        Direction::Left => (-px, 0.0),
+        Direction::Right => (px, 0.0),
+        Direction::Up => (0.0, -px),
+        Direction::Down => (0.0, px),
+    };
+
+    kept_within(
+        Bounds {
+            x: bounds.x + dx,
+            y: bounds.y + dy,
+            ..bounds
+        },
+        area,
+    )
+}
+
+/// Returns `bounds` with their right or bottom edge moved `px` pixels in
+/// `direction`, growing them rightwards and downwards and shrinking them
+/// leftwards and upwards, as the resize hotkeys do for a tiled window.
+pub fn resized_towards(
+    bounds: Bounds,
+    direction: Direction,
+    px: f64,
+    min_size: f64,
+    area: Bounds,
+) -> Bounds {
+    let (dimension, px) = match direction {
+        Direction::Left => (Dimension::Width, -px),
+        Direction::Right => (Dimension::Width, px),
+        Direction::Up => (Dimension::Height, -px),
+        Direction::Down => (Dimension::Height, px),
+    };
+
+    grown(bounds, dimension, px, min_size, area)
+}
+
+/// Returns `bounds` resized as i3 resizes a floating window, keeping their top
+/// left corner where it fits. Amounts in `ppt` are percentage points of
+/// `area`, and a grow or shrink giving no amount uses `default_px`.
+pub fn resized(
+    bounds: Bounds,
+    arg: ResizeArg,
+    default_px: f64,
+    min_size: f64,
+    area: Bounds,
+) -> Bounds {
+    let length = |dimension| match dimension {
+        Dimension::Width => area.width,
+        Dimension::Height => area.height,
+    };
+    let pixels = |amount: ResizeAmount, length: f64| {
+        amount.px.or(amount.ppt.map(|ppt| length * ppt / 100.0))
+    };
+
+    match arg {
+        ResizeArg::Grow(dimension, amount) => {
+            let px = pixels(amount, length(dimension)).unwrap_or(default_px);
+            grown(bounds, dimension, px, min_size, area)
+        }
+        ResizeArg::Shrink(dimension, amount) => {
+            let px = pixels(amount, length(dimension)).unwrap_or(default_px);
+            grown(bounds, dimension, -px, min_size, area)
+        }
+        ResizeArg::Set { width, height } => kept_within(
+            Bounds {
+                width: width
+                    .and_then(|w| pixels(w, area.width))
+                    .unwrap_or(bounds.width),
+                height: height
+                    .and_then(|h| pixels(h, area.height))
+                    .unwrap_or(bounds.height),
+                ..bounds
+            },
+            area,
+        ),
+    }
+}
+
+/// Returns `bounds` placed within `area` as `arg` asks.
+pub fn positioned(bounds: Bounds, arg: PositionArg, area: Bounds) -> Bounds {
+    let bounds = kept_within(bounds, area);
+    let right = area.x + area.width - bounds.width;
+    let bottom = area.y + area.height - bounds.height;
+
+    match arg {
+        PositionArg::Centre => Bounds {
+            x: area.x + (area.width - bounds.width) / 2.0,
+            y: area.y + (area.height - bounds.height) / 2.0,
+            ..bounds
+        },
+        PositionArg::Edge(edge) => match edge.into() {
+            Direction::Left => Bounds {
+                x: area.x,
+                ..bounds
+            },
+            Direction::Right => Bounds { x: right, ..bounds },
+            Direction::Up => Bounds {
+                y: area.y,
+                ..bounds
+            },
+            Direction::Down => Bounds {
+                y: bottom,
+                ..bounds
+            },
+        },
+        PositionArg::Half(edge) => {
+            let (half_width, half_height) = (area.width / 2.0, area.height / 2.0);
+            match edge.into() {
+                Direction::Left => Bounds {
+                    width: half_width,
+                    ..area
+                },
+                Direction::Right => Bounds {
+                    x: area.x + half_width,
+                    width: half_width,
+                    ..area
+                },
+                Direction::Up => Bounds {
+                    height: half_height,
+                    ..area
+                },
+                Direction::Down => Bounds {
+                    y: area.y + half_height,
+                    height: half_height,
+                    ..area
+                },
+            }
+        }
+    }
+}
+
+// Grow `bounds` by `px` pixels along `dimension`, or shrink them when `px` is
+// negative. Shrinking stops at `min_size`, or at the current size for bounds
+// already smaller than that.
+fn grown(bounds: Bounds, dimension: Dimension, px: f64, min_size: f64, area: Bounds) -> Bounds {
+    let resized = match dimension {
+        Dimension::Width => Bounds {
+            width: (bounds.width + px).max(min_size.min(bounds.width)),
+            ..bounds
+        },
+        Dimension::Height => Bounds {
+            height: (bounds.height + px).max(min_size.min(bounds.height)),
+            ..bounds
+        },
+    };
+
+    kept_within(resized, area)
+}
+
+// Shrink `bounds` to fit within `area`, then move them the least distance that
+// puts them wholly inside it.
+fn kept_within(bounds: Bounds, area: Bounds) -> Bounds {
+    let width = bounds.width.min(area.width);
+    let height = bounds.height.min(area.height);
+
+    Bounds {
+        x: bounds.x.clamp(area.x, area.x + area.width - width),
+        y: bounds.y.clamp(area.y, area.y + area.height - height),
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ctl::DirectionArg;
+
+    const AREA: Bounds = Bounds {
+        x: 0.0,
+        y: 37.0,
+        width: 1000.0,
+        height: 800.0,
+    };
+
+    const WINDOW: Bounds = Bounds {
+        x: 100.0,
+        y: 137.0,
+        width: 400.0,
+        height: 300.0,
+    };
+
+    #[test]
+    fn moved_stops_at_the_area_edges() {
+        let right = moved(WINDOW, Direction::Right, 50.0, AREA);
+        assert_eq!((right.x, right.y), (150.0, 137.0));
+
+        let up = moved(WINDOW, Direction::Up, 500.0, AREA);
+        assert_eq!((up.x, up.y), (100.0, AREA.y));
+
+        let down = moved(WINDOW, Direction::Down, 1000.0, AREA);
+        assert_eq!(down.y + down.height, AREA.y + AREA.height);
+        assert_eq!((down.width, down.height), (WINDOW.width, WINDOW.height));
+    }
+
+    #[test]
+    fn resized_towards_moves_the_right_or_bottom_edge() {
+        let wider = resized_towards(WINDOW, Direction::Right, 50.0, 200.0, AREA);
+        assert_eq!((wider.x, wider.width), (WINDOW.x, 450.0));
+
+        let shorter = resized_towards(WINDOW, Direction::Up, 50.0, 200.0, AREA);
+        assert_eq!((shorter.y, shorter.height), (WINDOW.y, 250.0));
+
+        let smallest = resized_towards(WINDOW, Direction::Left, 1000.0, 200.0, AREA);
+        assert_eq!(smallest.width, 200.0);
+    }
+
+    #[test]
+    fn resized_never_grows_a_small_window_by_shrinking() {
+        let small = Bounds {
+            width: 150.0,
+            ..WINDOW
+        };
+
+        let shrunk = resized_towards(small, Direction::Left, 50.0, 200.0, AREA);
+        assert_eq!(shrunk.width, 150.0);
+    }
+
+    #[test]
+    fn resized_reads_pixels_then_percentage_points() {
+        let px = ResizeAmount {
+            px: Some(20.0),
+            ppt: Some(10.0),
+        };
+        let ppt = ResizeAmount {
+            px: None,
+            ppt: Some(10.0),
+        };
+
+        let grown = resized(
+            WINDOW,
+            ResizeArg::Grow(Dimension::Width, px),
+            50.0,
+            200.0,
+            AREA,
+        );
+        assert_eq!(grown.width, 420.0);
+
+        let shrunk = resized(
+            WINDOW,
+            ResizeArg::Shrink(Dimension::Height, ppt),
+            50.0,
+            200.0,
+            AREA,
+        );
+        assert_eq!(shrunk.height, 220.0);
+
+        let default = ResizeArg::Grow(Dimension::Height, ResizeAmount::default());
+        assert_eq!(resized(WINDOW, default, 50.0, 200.0, AREA).height, 350.0);
+
+        let set = ResizeArg::Set {
+            width: Some(ppt),
+            height: None,
+        };
+        let set = resized(WINDOW, set, 50.0, 200.0, AREA);
+        assert_eq!((set.width, set.height), (100.0, WINDOW.height));
+    }
+
+    #[test]
+    fn resized_fits_the_window_within_the_area() {
+        let huge = ResizeAmount {
+            px: Some(5000.0),
+            ppt: None,
+        };
+
+        let grown = resized(
+            WINDOW,
+            ResizeArg::Grow(Dimension::Width, huge),
+            50.0,
+            200.0,
+            AREA,
+        );
+        assert_eq!((grown.x, grown.width), (AREA.x, AREA.width));
+    }
+
+    #[test]
+    fn positioned_centres_and_snaps() {
+        let centred = positioned(WINDOW, PositionArg::Centre, AREA);
+        assert_eq!((centred.x, centred.y), (300.0, 287.0));
+
+        let right = positioned(WINDOW, PositionArg::Edge(DirectionArg::Right), AREA);
+        assert_eq!((right.x, right.y), (600.0, WINDOW.y));
+
+        let bottom = positioned(WINDOW, PositionArg::Edge(DirectionArg::Down), AREA);
+        assert_eq!((bottom.x, bottom.y), (WINDOW.x, 537.0));
+
+        let left_half = positioned(WINDOW, PositionArg::Half(DirectionArg::Left), AREA);
+        assert_eq!(
+            (left_half.x, left_half.y, left_half.width, left_half.height),
+            (0.0, 37.0, 500.0, 800.0)
+        );
+
+        let bottom_half = positioned(WINDOW, PositionArg::Half(DirectionArg::Down), AREA);
+        assert_eq!(
+            (
+                bottom_half.x,
+                bottom_half.y,
+                bottom_half.width,
+                bottom_half.height
+            ),
+            (0.0, 437.0, 1000.0, 400.0)
+        );
+    }
+}
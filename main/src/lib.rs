@@ -1,12 +1,18 @@
 pub mod config;
 mod container;
+pub mod criteria;
 pub mod ctl;
 mod display;
 pub mod error;
 mod event_loop;
+mod floating;
 mod log;
+mod marks;
 mod poll;
+pub mod rules;
+mod scratchpad;
 mod status_bar;
 mod sys_info;
+mod title_strips;
 mod window;
 pub mod window_manager;
@@ -1,5 +1,9 @@
-use crate::{container::Axis, display};
-use core_graphics::{Direction, DisplayId, KeyCommand, WindowId};
+use crate::{
+    container::{Axis, Layout},
+    ctl::{BalanceArg, Command, FocusArg, ResizeArg},
+    display,
+};
+use core_graphics::{Bounds, Direction, DisplayId, KeyCommand, WindowId};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::{
@@ -108,6 +112,7 @@ pub enum Message {
     ReceivedWindowRemovedEvent(DisplayId, WindowId),
     ReceivedWindowFocusedEvent(WindowId),
     ReceivedKeyCommand(KeyCommand),
+    ReceivedCtlCommand(Command),
 
     WindowAdded(display::logical::Id, WindowId),
     WindowRemoved(display::logical::Id, WindowId),
@@ -115,7 +120,28 @@ pub enum Message {
     WindowMadeFloating(WindowId),
     WindowMadeManaged(WindowId),
     WindowResized(WindowId, Direction),
+    WindowMoved(WindowId, Direction),
     WindowSplitAlongAxis(WindowId, Axis),
+    LayoutChanged(WindowId, Layout),
+    FocusLevelChanged(FocusArg),
+    Balanced(BalanceArg),
+    ContainerResized(WindowId, ResizeArg),
+    FullscreenChanged(WindowId, bool),
+    StickyChanged(WindowId, bool),
+    WindowIgnoredByRule(WindowId),
+    WindowAssignedByRule(WindowId, display::logical::Id),
+    WindowMovedToScratchpad(WindowId),
+    ScratchpadWindowShown(WindowId),
+    ScratchpadWindowHidden(WindowId),
+    WindowMarked(WindowId, String),
+    MarkRemoved(Option<String>),
+    FocusedMatchingWindow(String, WindowId),
+    WindowMovedToMark(WindowId, String),
+    FocusedLastWindow(WindowId),
+    FloatingWindowPlaced(WindowId, Bounds),
+    Undone(display::logical::Id),
+    Redone(display::logical::Id),
+    WentBackToLogicalDisplay(display::logical::Id),
     ShiftedFocusInDirection(Direction),
     WindowMovedToLogicalDisplay(WindowId, display::logical::Id),
     FocusedLogicalDisplay(display::logical::Id),
@@ -128,7 +154,10 @@ pub enum Message {
     ToggleVerticalSplitKeyCommand,
     ToggleHorizontalSplitKeyCommand,
     ResizeWindowInDirectionKeyCommand(Direction),
+    MoveWindowInDirectionKeyCommand(Direction),
+    ToggleTabbedKeyCommand,
     ToggleWindowFloatingKeyCommand,
+    BalanceKeyCommand,
 }
 
 impl Log for Message {
@@ -140,6 +169,7 @@ impl Log for Message {
             ReceivedWindowRemovedEvent(_, _) => Level::Trace,
             ReceivedWindowFocusedEvent(_) => Level::Trace,
             ReceivedKeyCommand(_) => Level::Trace,
+            ReceivedCtlCommand(_) => Level::Trace,
 
             WindowAdded(_, _) => Level::Info,
             WindowRemoved(_, _) => Level::Info,
@@ -147,7 +177,28 @@ impl Log for Message {
             WindowMadeFloating(_) => Level::Info,
             WindowMadeManaged(_) => Level::Info,
             WindowResized(_, _) => Level::Info,
+            WindowMoved(_, _) => Level::Info,
             WindowSplitAlongAxis(_, _) => Level::Info,
+            LayoutChanged(_, _) => Level::Info,
+            FocusLevelChanged(_) => Level::Info,
+            Balanced(_) => Level::Info,
+            ContainerResized(_, _) => Level::Info,
+            FullscreenChanged(_, _) => Level::Info,
+            StickyChanged(_, _) => Level::Info,
+            WindowIgnoredByRule(_) => Level::Info,
+            WindowAssignedByRule(_, _) => Level::Info,
+            WindowMovedToScratchpad(_) => Level::Info,
+            ScratchpadWindowShown(_) => Level::Info,
+            ScratchpadWindowHidden(_) => Level::Info,
+            WindowMarked(_, _) => Level::Info,
+            MarkRemoved(_) => Level::Info,
+            FocusedMatchingWindow(_, _) => Level::Info,
+            WindowMovedToMark(_, _) => Level::Info,
+            FocusedLastWindow(_) => Level::Info,
+            FloatingWindowPlaced(_, _) => Level::Info,
+            Undone(_) => Level::Info,
+            Redone(_) => Level::Info,
+            WentBackToLogicalDisplay(_) => Level::Info,
             ShiftedFocusInDirection(_) => Level::Info,
             WindowMovedToLogicalDisplay(_, _) => Level::Info,
             FocusedLogicalDisplay(_) => Level::Info,
@@ -159,7 +210,10 @@ impl Log for Message {
             ToggleVerticalSplitKeyCommand => Level::Trace,
             ToggleHorizontalSplitKeyCommand => Level::Trace,
             ResizeWindowInDirectionKeyCommand(_) => Level::Trace,
+            MoveWindowInDirectionKeyCommand(_) => Level::Trace,
+            ToggleTabbedKeyCommand => Level::Trace,
             ToggleWindowFloatingKeyCommand => Level::Trace,
+            BalanceKeyCommand => Level::Trace,
         }
     }
 
@@ -177,6 +231,7 @@ impl Log for Message {
                 format!("received window focus event for window {w_id}")
             }
             ReceivedKeyCommand(kc) => format!("keyboard command input received {kc:?}"),
+            ReceivedCtlCommand(c) => format!("ctl command received {c:?}"),
 
             WindowAdded(l_id, w_id) => format!("added window {w_id} to {l_id}"),
             WindowRemoved(l_id, w_id) => {
@@ -186,7 +241,38 @@ impl Log for Message {
             WindowMadeFloating(w_id) => format!("toggle window {w_id} as floating"),
             WindowMadeManaged(w_id) => format!("toggle window {w_id} as managed"),
             WindowResized(w_id, d) => format!("resized window {w_id} {d}"),
+            WindowMoved(w_id, d) => format!("moved window {w_id} {d}"),
             WindowSplitAlongAxis(w_id, d) => format!("split window {w_id} along {d} axis"),
+            LayoutChanged(w_id, l) => format!("set layout of window {w_id}'s container to {l}"),
+            FocusLevelChanged(FocusArg::Parent) => "focused parent container".into(),
+            FocusLevelChanged(FocusArg::Child) => "focused child container".into(),
+            Balanced(BalanceArg::Container) => "balanced focused container".into(),
+            Balanced(BalanceArg::Display) => "balanced active logical display".into(),
+            ContainerResized(w_id, r) => format!("resized window {w_id}'s container with {r:?}"),
+            FullscreenChanged(w_id, true) => format!("made window {w_id} fullscreen"),
+            FullscreenChanged(w_id, false) => format!("returned window {w_id} from fullscreen"),
+            StickyChanged(w_id, true) => format!("made window {w_id} sticky"),
+            StickyChanged(w_id, false) => format!("made window {w_id} no longer sticky"),
+            WindowIgnoredByRule(w_id) => format!("ignored window {w_id} by rule"),
+            WindowAssignedByRule(w_id, l_id) => format!("assigned window {w_id} to {l_id} by rule"),
+            WindowMovedToScratchpad(w_id) => format!("moved window {w_id} to the scratchpad"),
+            ScratchpadWindowShown(w_id) => format!("showed scratchpad window {w_id}"),
+            ScratchpadWindowHidden(w_id) => format!("hid scratchpad window {w_id}"),
+            WindowMarked(w_id, mark) => format!("marked window {w_id} as {mark:?}"),
+            MarkRemoved(Some(mark)) => format!("removed mark {mark:?}"),
+            MarkRemoved(None) => "removed every mark".into(),
+            FocusedMatchingWindow(criteria, w_id) => {
+                format!("focused window {w_id} matching {criteria}")
+            }
+            WindowMovedToMark(w_id, mark) => format!("moved window {w_id} to mark {mark:?}"),
+            FocusedLastWindow(w_id) => format!("focused last window {w_id}"),
+            FloatingWindowPlaced(w_id, b) => format!(
+                "placed floating window {w_id} at ({}, {}) sized {}x{}",
+                b.x, b.y, b.width, b.height
+            ),
+            Undone(l_id) => format!("undid last change on {l_id}"),
+            Redone(l_id) => format!("redid last undone change on {l_id}"),
+            WentBackToLogicalDisplay(l_id) => format!("went back to {l_id}"),
             ShiftedFocusInDirection(d) => format!("shifted focus {d}"),
             WindowMovedToLogicalDisplay(w_id, l_id) => format!("moved window {w_id} to {l_id}"),
             FocusedLogicalDisplay(l_id) => format!("focused {l_id}"),
@@ -210,9 +296,14 @@ impl Log for Message {
             ResizeWindowInDirectionKeyCommand(d) => {
                 format!("resize window {d} key command input received")
             }
+            MoveWindowInDirectionKeyCommand(d) => {
+                format!("move window {d} key command input received")
+            }
+            ToggleTabbedKeyCommand => "toggle tabbed key command input received".into(),
             ToggleWindowFloatingKeyCommand => {
                 "toggle window floating key command input received".into()
             }
+            BalanceKeyCommand => "balance key command input received".into(),
         }
     }
 }
@@ -0,0 +1,185 @@
+use crate::error::{Error, Result};
+use core_graphics::WindowId;
+use std::collections::BTreeSet;
+
+/// The names a window has been marked with, in the style of i3's `mark`.
+#[derive(Debug, Default)]
+pub struct Marks(BTreeSet<String>);
+
+impl Marks {
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains(name)
+    }
+
+    pub fn add(&mut self, name: String) {
+        self.0.insert(name);
+    }
+
+    /// Remove the mark `name`, or every mark when `name` is `None`.
+    pub fn remove(&mut self, name: Option<&str>) {
+        match name {
+            Some(name) => {
+                self.0.remove(name);
+            }
+            None => self.0.clear(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+/// Marks the window `id` with `name`. A mark names one window at a time, so it
+/// is taken from any other of `windows` that has it. Unless `add`, the window's
+/// other marks are replaced.
+pub fn mark<'a>(
+    windows: impl IntoIterator<Item = (WindowId, &'a mut Marks)>,
+    id: WindowId,
+    name: &str,
+    add: bool,
+) -> Result<()> {
+    let windows: Vec<_> = windows.into_iter().collect();
+    if !windows.iter().any(|(w, _)| *w == id) {
+        return Err(Error::WindowNotFound);
+    }
+
+    for (w, marks) in windows {
+        if w == id {
+            if !add {
+                marks.remove(None);
+            }
+            marks.add(name.to_string());
+        } else {
+            marks.remove(Some(name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the one of `windows` marked with `name`.
+pub fn marked<'a>(
+    windows: impl IntoIterator<Item = (WindowId, &'a Marks)>,
+    name: &str,
+) -> Option<WindowId> {
+    windows
+        .into_iter()
+        .find(|(_, marks)| marks.contains(name))
+        .map(|(w, _)| w)
+}
+
+/// Every mark set on any of `windows`, sorted by name.
+pub fn sorted<'a>(windows: impl IntoIterator<Item = &'a Marks>) -> Vec<String> {
+    let mut marks: Vec<_> = windows
+        .into_iter()
+        .flat_map(|m| m.iter().map(str::to_string))
+        .collect();
+    marks.sort();
+    marks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn wid(n: u32) -> WindowId {
+        WindowId::from(n)
+    }
+
+    fn marks(names: &[&str]) -> Marks {
+        let mut marks = Marks::default();
+        for name in names {
+            marks.add(name.to_string());
+        }
+        marks
+    }
+
+    fn names(marks: &Marks) -> Vec<&str> {
+        marks.iter().collect()
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let mut m = marks(&["b", "a"]);
+        assert!(m.contains("a"));
+        assert_eq!(names(&m), ["a", "b"]);
+
+        m.remove(Some("a"));
+        assert!(!m.contains("a"));
+        assert_eq!(names(&m), ["b"]);
+
+        m.remove(Some("missing"));
+        assert_eq!(names(&m), ["b"]);
+    }
+
+    #[test]
+    fn remove_without_a_name_clears_every_mark() {
+        let mut m = marks(&["a", "b"]);
+        m.remove(None);
+        assert_eq!(names(&m), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn a_mark_names_one_window_at_a_time() {
+        let mut one = marks(&["a", "b"]);
+        let mut two = marks(&["c"]);
+
+        let windows = [(wid(1), &mut one), (wid(2), &mut two)];
+        mark(windows, wid(2), "a", true).unwrap();
+
+        assert_eq!(names(&one), ["b"]);
+        assert_eq!(names(&two), ["a", "c"]);
+    }
+
+    #[test]
+    fn marking_without_add_replaces_the_windows_marks() {
+        let mut one = marks(&["a", "b"]);
+        let mut two = marks(&["c"]);
+
+        let windows = [(wid(1), &mut one), (wid(2), &mut two)];
+        mark(windows, wid(1), "c", false).unwrap();
+
+        assert_eq!(names(&one), ["c"]);
+        assert_eq!(names(&two), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn marking_an_untracked_window_changes_nothing() {
+        let mut one = marks(&["a"]);
+
+        let result = mark([(wid(1), &mut one)], wid(2), "a", false);
+
+        assert!(matches!(result, Err(Error::WindowNotFound)));
+        assert_eq!(names(&one), ["a"]);
+    }
+
+    #[test]
+    fn marked_finds_the_window_with_a_mark() {
+        let windows = [(wid(1), marks(&["a"])), (wid(2), marks(&["b"]))];
+        let windows = windows.iter().map(|(w, m)| (*w, m));
+
+        assert_eq!(marked(windows.clone(), "b"), Some(wid(2)));
+        assert_eq!(marked(windows, "c"), None);
+    }
+
+    #[test]
+    fn a_closed_windows_marks_are_gone() {
+        let mut windows = HashMap::from([(wid(1), marks(&[])), (wid(2), marks(&[]))]);
+        let all = windows.iter_mut().map(|(&w, m)| (w, m));
+        mark(all, wid(2), "call", true).unwrap();
+        assert_eq!(sorted(windows.values()), ["call"]);
+
+        windows.remove(&wid(2));
+
+        assert_eq!(sorted(windows.values()), Vec::<String>::new());
+        assert_eq!(marked(windows.iter().map(|(&w, m)| (w, m)), "call"), None);
+    }
+
+    #[test]
+    fn sorted_lists_every_windows_marks_by_name() {
+        let windows = [marks(&["m", "z"]), marks(&[]), marks(&["a"])];
+        assert_eq!(sorted(&windows), ["a", "m", "z"]);
+    }
+}
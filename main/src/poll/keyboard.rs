@@ -142,8 +142,12 @@ pub extern "C" fn event_callback(
         HotKey::VERTICAL_SPLIT => Some(KeyCommand::ToggleVerticalSplit),
         HotKey::HORIZONTAL_SPLIT => Some(KeyCommand::ToggleHorizontalSplit),
 
+        HotKey::TOGGLE_TABBED => Some(KeyCommand::ToggleTabbed),
+
         HotKey::TOGGLE_FLOATING => Some(KeyCommand::ToggleFloating),
 
+        HotKey::BALANCE => Some(KeyCommand::Balance),
+
         _ => None,
     };
 
@@ -0,0 +1,259 @@
+use crate::criteria::{Criteria, Regex};
+use serde::{Deserialize, Serialize};
+
+/// A rule applied to a window when it first appears, in the style of i3's
+/// `for_window`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    pub criteria: Criteria,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    /// Leave the window floating rather than tiling it.
+    Float,
+    /// Tile the window on the logical display with this number, rather than
+    /// the active one.
+    Assign(usize),
+    Mark(String),
+    /// Give the window this size in pixels, keeping its position. Only
+    /// floating windows are sized; a tiled window's size is set by its split.
+    Size {
+        width: f64,
+        height: f64,
+    },
+    /// Leave the window alone entirely; it is neither tiled nor tracked.
+    Ignore,
+}
+
+impl Rule {
+    /// The rules applied before any configured ones unless turned off with
+    /// `--no-default-rules`: settings panes and password managers float, since
+    /// they are small and short lived.
+    pub fn defaults() -> Vec<Self> {
+        let regex = |source: &str| Some(Regex::new(source).unwrap());
+        let float = |app_name: &str, title: Option<&str>| Self {
+            criteria: Criteria {
+                app_name: regex(app_name),
+                title: title.and_then(regex),
+                ..Default::default()
+            },
+            action: Action::Float,
+        };
+
+        vec![
+            float("^System Settings$", None),
+            float("^Finder$", Some(" Info$")),
+            float("^1Password$", None),
+        ]
+    }
+
+    /// Parse a rule from criteria followed by an action, for example
+    /// `[app_name="^System Settings$"] float` or `[app_name=Slack] assign 4`.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (criteria, action) = Criteria::parse_leading(s)?;
+        let tokens = tokenise(action)?;
+        let mut tokens = tokens.iter().map(String::as_str);
+
+        let action = match tokens.next() {
+            Some("float") => Action::Float,
+            Some("ignore") => Action::Ignore,
+            Some("assign") => {
+                let value = tokens.next().ok_or("expected a logical display number")?;
+                Action::Assign(logical_display_number(value)?)
+            }
+            Some("mark") => Action::Mark(tokens.next().ok_or("expected a mark")?.to_string()),
+            Some("size") => {
+                let mut length = || -> Result<f64, String> {
+                    let value = tokens.next().ok_or("expected a width and height")?;
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid length: {value}"))
+                };
+                Action::Size {
+                    width: length()?,
+                    height: length()?,
+                }
+            }
+            Some(unknown) => return Err(format!("unknown rule action: {unknown}")),
+            None => return Err(format!("expected an action in rule: {s}")),
+        };
+
+        if let Some(extra) = tokens.next() {
+            return Err(format!("unexpected {extra} after rule action"));
+        }
+
+        Ok(Self { criteria, action })
+    }
+
+    /// Parse an assignment in the style of i3's `assign`: criteria followed by
+    /// the number of a logical display, optionally preceded by `→`,
+    /// `workspace` or `number`, for example `[app_name="^Slack$"] 4`.
+    pub fn parse_assign(s: &str) -> Result<Self, String> {
+        let (criteria, rest) = Criteria::parse_leading(s)?;
+        let tokens = tokenise(rest)?;
+
+        let number = match tokens.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+            [number]
+            | ["→" | "workspace" | "number", number]
+            | ["→", "workspace" | "number", number] => logical_display_number(number)?,
+            _ => {
+                return Err(format!(
+                    "expected a logical display number in assignment: {s}"
+                ));
+            }
+        };
+
+        Ok(Self {
+            criteria,
+            action: Action::Assign(number),
+        })
+    }
+}
+
+// Parse the number of a logical display, which is one of 0 to 9, the same as
+// the hotkeys reach.
+fn logical_display_number(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|n| (0..=9).contains(n))
+        .ok_or_else(|| format!("invalid logical display number: {value}"))
+}
+
+/// Split `s` on whitespace, keeping text within double quotes together and
+/// dropping the quotes.
+fn tokenise(s: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut in_quotes = false;
+    let mut in_token = false;
+
+    for c in s.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                in_token = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut token));
+                    in_token = false;
+                }
+            }
+            c => {
+                token.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("unterminated quote in rule: {s}"));
+    }
+    if in_token {
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::criteria::WindowProperties;
+    use core_graphics::WindowId;
+
+    #[test]
+    fn parse_reads_criteria_and_action() {
+        let rule = Rule::parse(r#"[app_name="System Settings" subrole=AXDialog] float"#).unwrap();
+
+        assert_eq!(
+            rule,
+            Rule {
+                criteria: Criteria {
+                    app_name: Some(Regex::new("System Settings").unwrap()),
+                    subrole: Some(Regex::new("AXDialog").unwrap()),
+                    ..Default::default()
+                },
+                action: Action::Float,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_reads_action_arguments() {
+        assert_eq!(
+            Rule::parse("[app_name=Slack] assign 4").unwrap().action,
+            Action::Assign(4)
+        );
+        assert_eq!(
+            Rule::parse("[pid=42] size 640 480").unwrap().action,
+            Action::Size {
+                width: 640.0,
+                height: 480.0
+            }
+        );
+        assert_eq!(
+            Rule::parse(r#"[title="a b"] mark "c d""#).unwrap().action,
+            Action::Mark("c d".into())
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_rules() {
+        assert!(Rule::parse("[app_name=Slack]").is_err());
+        assert!(Rule::parse("app_name=Slack float").is_err());
+        assert!(Rule::parse("[colour=red] float").is_err());
+        assert!(Rule::parse("[app_name=\"Slack] float").is_err());
+        assert!(Rule::parse("[app_name=Slack] size 640").is_err());
+        assert!(Rule::parse("[app_name=Slack] float now").is_err());
+    }
+
+    #[test]
+    fn parse_assign_reads_criteria_and_number() {
+        let rule = Rule::parse_assign(r#"[app_name="^Slack$"] 4"#).unwrap();
+
+        assert_eq!(rule.criteria.app_name, Some(Regex::new("^Slack$").unwrap()));
+        assert_eq!(rule.action, Action::Assign(4));
+        for s in [
+            "[app_name=Slack] workspace 4",
+            "[app_name=Slack] → number 4",
+        ] {
+            assert_eq!(Rule::parse_assign(s).unwrap().action, Action::Assign(4));
+        }
+    }
+
+    #[test]
+    fn parse_assign_rejects_malformed_assignments() {
+        assert!(Rule::parse_assign("[app_name=Slack]").is_err());
+        assert!(Rule::parse_assign("[app_name=Slack] four").is_err());
+        assert!(Rule::parse_assign("[app_name=Slack] 4 5").is_err());
+        assert!(Rule::parse_assign("[app_name=Slack] 42").is_err());
+        assert!(Rule::parse("[app_name=Slack] assign 10").is_err());
+        assert!(Rule::parse_assign("app_name=Slack 4").is_err());
+    }
+
+    #[test]
+    fn default_rules_float_only_finder_info_panes() {
+        let finder_info = WindowProperties {
+            id: WindowId::from(1u32),
+            app_name: Some("Finder"),
+            title: Some("notes.txt Info"),
+            pid: 1,
+            layer: 0,
+            role: None,
+            subrole: None,
+            floating: false,
+            marks: Vec::new(),
+        };
+        let finder = WindowProperties {
+            title: Some("Downloads"),
+            ..finder_info.clone()
+        };
+
+        let info_panes = &Rule::defaults()[1].criteria;
+        assert!(info_panes.matches(&finder_info));
+        assert!(!info_panes.matches(&finder));
+    }
+}
@@ -0,0 +1,85 @@
+use core_graphics::WindowId;
+use std::collections::VecDeque;
+
+/// What `scratchpad show` does next.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// Minimise the shown and focused scratchpad window again.
+    Hide(WindowId),
+    /// Focus the scratchpad window that is shown but not focused.
+    Focus(WindowId),
+    /// Unminimise the scratchpad window, which has already been moved to the
+    /// back of the queue.
+    Show(WindowId),
+}
+
+/// Decides what `scratchpad show` does, in the style of i3: a shown and
+/// focused scratchpad window is hidden again, a shown one is focused, and
+/// otherwise the window at the front of `queue` is shown and moved to the back
+/// so that the next show cycles on to another window. Returns `None` if the
+/// scratchpad is empty.
+pub fn next_step(
+    queue: &mut VecDeque<WindowId>,
+    is_shown: impl Fn(WindowId) -> bool,
+    focused: Option<WindowId>,
+) -> Option<Step> {
+    if let Some(shown) = queue.iter().copied().find(|&w| is_shown(w)) {
+        return Some(if focused == Some(shown) {
+            Step::Hide(shown)
+        } else {
+            Step::Focus(shown)
+        });
+    }
+
+    let next = queue.pop_front()?;
+    queue.push_back(next);
+    Some(Step::Show(next))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn wid(n: u32) -> WindowId {
+        WindowId::from(n)
+    }
+
+    fn queue(ids: &[u32]) -> VecDeque<WindowId> {
+        ids.iter().copied().map(wid).collect()
+    }
+
+    #[test]
+    fn an_empty_scratchpad_has_nothing_to_show() {
+        let mut q = queue(&[]);
+        assert_eq!(next_step(&mut q, |_| false, None), None);
+    }
+
+    #[test]
+    fn shows_the_front_window_and_cycles_the_queue() {
+        let mut q = queue(&[1, 2, 3]);
+
+        assert_eq!(next_step(&mut q, |_| false, None), Some(Step::Show(wid(1))));
+        assert_eq!(q, queue(&[2, 3, 1]));
+
+        assert_eq!(next_step(&mut q, |_| false, None), Some(Step::Show(wid(2))));
+        assert_eq!(q, queue(&[3, 1, 2]));
+    }
+
+    #[test]
+    fn hides_a_shown_and_focused_window() {
+        let mut q = queue(&[1, 2]);
+
+        let step = next_step(&mut q, |w| w == wid(2), Some(wid(2)));
+        assert_eq!(step, Some(Step::Hide(wid(2))));
+        assert_eq!(q, queue(&[1, 2]));
+    }
+
+    #[test]
+    fn focuses_a_shown_but_unfocused_window() {
+        let mut q = queue(&[1, 2]);
+
+        let step = next_step(&mut q, |w| w == wid(2), Some(wid(7)));
+        assert_eq!(step, Some(Step::Focus(wid(2))));
+        assert_eq!(q, queue(&[1, 2]));
+    }
+}
@@ -0,0 +1,56 @@
+use crate::container::TitleStrip;
+use core_graphics::Bounds;
+use foundation::{Colour, Label, Window};
+
+/// The title strips drawn above the children of every visible stacked split.
+///
+/// Each strip is its own borderless window, so the set is torn down and
+/// rebuilt whenever the layout is applied rather than patched in place.
+#[derive(Default)]
+pub struct TitleStrips {
+    windows: Vec<Window>,
+}
+
+impl TitleStrips {
+    const LABEL_INSET: f64 = 5.0;
+
+    /// Replace the drawn strips with `strips`, each labelled with its title.
+    pub fn draw(&mut self, strips: impl IntoIterator<Item = (TitleStrip, String)>) {
+        self.close();
+
+        let main_display_bounds = core_graphics::Display::main_display_bounds();
+
+        for (strip, title) in strips {
+            // Core Graphics bounds have their origin at the top left of the
+            // main display, whereas AppKit windows have theirs at the bottom left.
+            let window_bounds = Bounds {
+                y: main_display_bounds.height - (strip.bounds.y + strip.bounds.height),
+                ..strip.bounds
+            };
+
+            let mut window = Window::new(window_bounds);
+            window.set_background_colour(if strip.is_active {
+                Colour::Blue
+            } else {
+                Colour::Black
+            });
+
+            let label_bounds = Bounds {
+                x: Self::LABEL_INSET,
+                y: 0.0,
+                width: strip.bounds.width - 2.0 * Self::LABEL_INSET,
+                height: strip.bounds.height,
+            };
+            window.add_element_to_content_view(Label::new(label_bounds, title, Colour::White));
+            window.display();
+
+            self.windows.push(window);
+        }
+    }
+
+    pub fn close(&mut self) {
+        for mut window in self.windows.drain(..) {
+            window.close();
+        }
+    }
+}
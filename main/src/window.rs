@@ -1,3 +1,6 @@
+use crate::container::TreePosition;
+use crate::criteria::WindowProperties;
+use crate::marks::Marks;
 use crate::{error::Error, error::Result};
 use core_graphics::Bounds;
 use std::hash::Hash;
@@ -17,6 +20,19 @@ pub(crate) struct Window {
     /// window ID. This causes issues with the minimisation/un-minimisation
     /// process performed during logical display focus shift; so keep track.
     is_minimised: bool,
+    /// The names the window has been marked with. A mark names at most one
+    /// window at a time.
+    marks: Marks,
+    /// The window's accessibility role and subrole, read once when it is
+    /// first tracked, for matching against criteria.
+    role: Option<String>,
+    subrole: Option<String>,
+    /// Where the window last sat in its logical display's tree before being
+    /// floated, so that tiling it again puts it back there.
+    tiled_position: Option<TreePosition>,
+    /// The window's bounds when it was last tiled again after floating, so
+    /// that floating it again puts it back there.
+    floating_bounds: Option<Bounds>,
 }
 
 impl Hash for Window {
@@ -43,11 +59,16 @@ impl TryFrom<core_graphics::Window> for Window {
         ax_window.try_focus().map_err(Error::AxUi)?;
 
         Ok(Self {
+            role: ax_window.role().ok(),
+            subrole: ax_window.subrole().ok(),
             bounds: value.bounds().clone(),
             cg: value,
             ax: ax_window,
             is_floating: false,
             is_minimised: false,
+            marks: Marks::default(),
+            tiled_position: None,
+            floating_bounds: None,
         })
     }
 }
@@ -56,6 +77,21 @@ impl Window {
         &self.bounds
     }
 
+    /// The window's bounds as they are on screen, which for a floating window
+    /// may have changed with the mouse since they were last set, falling back
+    /// to the bounds last set.
+    pub(crate) fn bounds_on_screen(&self) -> Bounds {
+        match (self.ax.position(), self.ax.size()) {
+            (Ok(position), Ok(size)) => Bounds {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+            },
+            _ => self.bounds,
+        }
+    }
+
     pub(crate) fn ax(&self) -> &ax_ui::Window {
         &self.ax
     }
@@ -72,10 +108,45 @@ impl Window {
         self.is_minimised
     }
 
+    pub(crate) fn marks(&self) -> &Marks {
+        &self.marks
+    }
+
+    pub(crate) fn marks_mut(&mut self) -> &mut Marks {
+        &mut self.marks
+    }
+
+    /// The window's properties, for matching against criteria.
+    pub(crate) fn properties(&self) -> WindowProperties<'_> {
+        WindowProperties {
+            role: self.role.as_deref(),
+            subrole: self.subrole.as_deref(),
+            floating: self.is_floating,
+            marks: self.marks.iter().collect(),
+            ..WindowProperties::of(&self.cg)
+        }
+    }
+
     pub(crate) fn set_floating(&mut self, is_floating: bool) {
         self.is_floating = is_floating;
     }
 
+    pub(crate) fn set_tiled_position(&mut self, position: Option<TreePosition>) {
+        self.tiled_position = position;
+    }
+
+    pub(crate) fn take_tiled_position(&mut self) -> Option<TreePosition> {
+        self.tiled_position.take()
+    }
+
+    pub(crate) fn floating_bounds(&self) -> Option<Bounds> {
+        self.floating_bounds
+    }
+
+    pub(crate) fn set_floating_bounds(&mut self, bounds: Bounds) {
+        self.floating_bounds = Some(bounds);
+    }
+
     pub(crate) fn unminimise(&mut self) -> Result<()> {
         self.ax.unminimise().map_err(Error::AxUi)?;
         self.is_minimised = false;
@@ -101,6 +172,20 @@ impl Window {
         Ok(())
     }
 
+    /// Move the window to the centre of `area`, keeping its size where it
+    /// fits within `area`.
+    pub fn centre_in(&mut self, area: Bounds) -> Result<()> {
+        let width = self.bounds.width.min(area.width);
+        let height = self.bounds.height.min(area.height);
+
+        self.update_bounds(Bounds {
+            x: area.x + (area.width - width) / 2.0,
+            y: area.y + (area.height - height) / 2.0,
+            width,
+            height,
+        })
+    }
+
     pub fn update_bounds(&mut self, new_bounds: Bounds) -> Result<()> {
         self.bounds = new_bounds;
 
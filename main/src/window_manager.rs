@@ -1,49 +1,63 @@
 use crate::display::Uninitialised;
 use crate::log::Prefix;
 use crate::{
-    config::Config,
-    container,
-    ctl::{CTL_SOCK, WmToCtlMessage},
+    config::{Config, DEFAULT_MIN_WINDOW_SIZE, DEFAULT_MOVE_AMOUNT, DEFAULT_RESIZE_AMOUNT},
+    container::{self, LayoutChange},
+    criteria::{Criteria, WindowProperties},
+    ctl::{
+        BalanceArg, CTL_SOCK, Command, CtlToWmMessage, FocusArg, FullscreenArg, MarkArg,
+        PositionArg, ResizeArg, ScratchpadArg, StickyArg, WmToCtlMessage, WorkspaceArg,
+    },
     display::{self, Displays, logical, physical},
     error::{Error, Result},
     event_loop::{self, EventLoop},
+    floating,
     log::{
         Log, Logger,
         Message::{
-            FocusLogicalDisplayKeyCommand, MoveFocusedWindowToLogicalDisplayKeyCommand,
-            OpenTerminalKeyCommand, ReceivedKeyCommand, ReceivedWindowAddedEvent,
-            ReceivedWindowFocusedEvent, ReceivedWindowRemovedEvent,
-            ResizeWindowInDirectionKeyCommand, ShiftFocusInDirectionKeyCommand,
-            ToggleHorizontalSplitKeyCommand, ToggleVerticalSplitKeyCommand,
-            ToggleWindowFloatingKeyCommand, WindowAdded, WindowMadeFloating, WindowMadeManaged,
-            WindowMovedToLogicalDisplay, WindowRemoved, WindowResized, WindowSplitAlongAxis,
+            BalanceKeyCommand, Balanced, ContainerResized, FloatingWindowPlaced, FocusLevelChanged,
+            FocusLogicalDisplayKeyCommand, FocusedLastWindow, FocusedMatchingWindow,
+            FullscreenChanged, LayoutChanged, MarkRemoved,
+            MoveFocusedWindowToLogicalDisplayKeyCommand, MoveWindowInDirectionKeyCommand,
+            OpenTerminalKeyCommand, ReceivedCtlCommand, ReceivedKeyCommand,
+            ReceivedWindowAddedEvent, ReceivedWindowFocusedEvent, ReceivedWindowRemovedEvent,
+            Redone, ResizeWindowInDirectionKeyCommand, ScratchpadWindowHidden,
+            ScratchpadWindowShown, ShiftFocusInDirectionKeyCommand, StickyChanged,
+            ToggleHorizontalSplitKeyCommand, ToggleTabbedKeyCommand, ToggleVerticalSplitKeyCommand,
+            ToggleWindowFloatingKeyCommand, Undone, WentBackToLogicalDisplay, WindowAdded,
+            WindowAssignedByRule, WindowIgnoredByRule, WindowMadeFloating, WindowMadeManaged,
+            WindowMarked, WindowMoved, WindowMovedToLogicalDisplay, WindowMovedToMark,
+            WindowMovedToScratchpad, WindowRemoved, WindowResized, WindowSplitAlongAxis,
         },
     },
+    marks,
     poll::{ChannelSource, Event, KeyboardHandler, Mux, Timer, WorkspaceEvent, WorkspaceObserver},
+    rules, scratchpad,
     status_bar::StatusBar,
+    title_strips::TitleStrips,
     window::Window,
 };
 use core_foundation::{CFRunLoopGetCurrent, CFRunLoopRunInMode, kCFRunLoopDefaultMode};
-use core_graphics::{Direction, DisplayId, KeyCommand, WindowId};
+use core_graphics::{Bounds, Direction, DisplayId, KeyCommand, WindowId};
 use foundation::Colour;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     io::Read,
     os::unix::net::UnixListener,
     time::Duration,
 };
 
-// Arbitrary reasonable constant that stop windows getting too
-// small. When this value is too small, the OS doesn't let the
-// smaller window get smaller, but this code will make the larger
-// window get larger and thus they overlap.
-pub(crate) const MIN_WINDOW_SIZE: f64 = 200.0;
-pub(crate) const RESIZE_AMOUNT: f64 = 50.0;
-
 pub struct WindowManager {
     /// A map between window IDs reported by CoreGraphics, and our managed
     /// window objects.
     windows: HashMap<WindowId, Window>,
+    /// The windows moved to the scratchpad, in the order `scratchpad show`
+    /// cycles through them. They stay in `windows` as floating windows, and
+    /// are minimised while hidden.
+    scratchpad: VecDeque<WindowId>,
+    /// The windows a rule has said to ignore. They are neither tiled nor
+    /// tracked in `windows`.
+    ignored: HashSet<WindowId>,
     /// The delegate display manager.
     displays: Displays<display::Initialised>,
     /// A logger to that produces logs prefixed with "WM".
@@ -54,6 +68,9 @@ pub struct WindowManager {
     /// Core Graphics. These hold information about what logical displays exist
     /// on each physical display, and which one has global focus.
     status_bars: HashMap<DisplayId, StatusBar>,
+    /// The title strips drawn above the children of stacked splits on every
+    /// physical display.
+    title_strips: TitleStrips,
 }
 
 impl WindowManager {
@@ -74,19 +91,22 @@ impl WindowManager {
 
         let displays = Displays::new();
         let displays =
-            displays.add_first_physical(first_id.into(), first_cg.bounds, config.into())?;
+            displays.add_first_physical(first_id.into(), first_cg.bounds, (&config).into())?;
 
         let mut wm = Self {
             windows: Default::default(),
+            scratchpad: Default::default(),
+            ignored: Default::default(),
             displays,
             logger: Logger::try_new("/dev/stdout", config.log_level, Prefix::WINDOW_MANAGER)
                 .map_err(Error::CreateLogger)?,
             config,
             status_bars: Default::default(),
+            title_strips: Default::default(),
         };
 
         for window in first_cg.windows {
-            wm.start_managing_window(window)?;
+            wm.start_managing_window(first_id.into(), window)?;
         }
 
         let lids: Vec<_> = wm
@@ -101,10 +121,10 @@ impl WindowManager {
         for (id, cg_display) in cg_displays {
             // First CoreGraphics display detected is chosen to be the active physical display
             wm.displays
-                .add_physical(id.into(), cg_display.bounds, config.into());
+                .add_physical(id.into(), cg_display.bounds, (&wm.config).into());
 
             for window in cg_display.windows {
-                wm.start_managing_window(window)?;
+                wm.start_managing_window(id.into(), window)?;
             }
 
             let lids: Vec<_> = wm.displays.logical_ids(id.into()).into_iter().collect();
@@ -128,9 +148,17 @@ impl WindowManager {
     //  4. Add it to some display, delegating to the `WindowManager`'s display
     //    manager, `Displays`.
     //  5. Add it to the `WindowManager`'s map of managed windows.
-    fn start_managing_window(&mut self, window: core_graphics::Window) -> Result<()> {
+    // Windows that a rule ignores or floats are not added to any display, and
+    // windows that a rule assigns to another logical display are added there.
+    fn start_managing_window(
+        &mut self,
+        pid: physical::Id,
+        window: core_graphics::Window,
+    ) -> Result<()> {
         let window_id = window.number();
-        let w = Window::try_from(window)?;
+        let Some((w, actions)) = self.adopt_window(window)? else {
+            return Ok(());
+        };
         let min_size = w.ax().min_size().unwrap_or_default();
         let cw = container::Window {
             id: window_id,
@@ -138,8 +166,100 @@ impl WindowManager {
             min_height: min_size.height,
         };
 
+        if let Some(lid) = assigned_logical_display(&actions)
+            && lid != self.displays.active_logical_display_id()
+        {
+            return self.assign_window(w, cw, lid, pid, &actions);
+        }
+
         self.displays.add_window(cw)?;
         self.windows.insert(window_id, w);
+        self.apply_window_rules(window_id, &actions)?;
+        Ok(())
+    }
+
+    /// Returns the actions of every rule matching a window, in the order the
+    /// rules are configured.
+    fn matching_rule_actions(&self, cg: &core_graphics::Window) -> Vec<rules::Action> {
+        let ax = ax_ui::Window::new(cg.owner_pid(), cg.number()).ok();
+        let role = ax.as_ref().and_then(|ax| ax.role().ok());
+        let subrole = ax.as_ref().and_then(|ax| ax.subrole().ok());
+
+        let properties = WindowProperties {
+            role: role.as_deref(),
+            subrole: subrole.as_deref(),
+            ..WindowProperties::of(cg)
+        };
+
+        self.config
+            .rules
+            .iter()
+            .filter(|rule| rule.criteria.matches(&properties))
+            .map(|rule| rule.action.clone())
+            .collect()
+    }
+
+    // To adopt a newly appeared window, apply the rules matching it that decide
+    // whether it is tiled:
+    //  - If one ignores it, remember to leave it alone.
+    //  - If one floats it, track it as a floating window and apply the rest of
+    //    the rules to it.
+    //  - Otherwise return it along with the rules' actions, for the caller to
+    //    tile it and then apply the rest.
+    fn adopt_window(
+        &mut self,
+        cg: core_graphics::Window,
+    ) -> Result<Option<(Window, Vec<rules::Action>)>> {
+        let window_id = cg.number();
+        let actions = self.matching_rule_actions(&cg);
+
+        if actions.contains(&rules::Action::Ignore) {
+            self.ignored.insert(window_id);
+            WindowIgnoredByRule(window_id).log(&mut self.logger);
+            return Ok(None);
+        }
+
+        let mut window = Window::try_from(cg)?;
+        if actions.contains(&rules::Action::Float) {
+            window.set_floating(true);
+            let lid = self.displays.logical_under(*window.bounds());
+            self.displays.add_floating(window_id, lid);
+            self.windows.insert(window_id, window);
+            self.apply_window_rules(window_id, &actions)?;
+
+            WindowMadeFloating(window_id).log(&mut self.logger);
+            return Ok(None);
+        }
+
+        Ok(Some((window, actions)))
+    }
+
+    /// Apply the rule actions that act on a window once it is tracked: marking
+    /// it, and sizing it when it is floating.
+    fn apply_window_rules(&mut self, id: WindowId, actions: &[rules::Action]) -> Result<()> {
+        for action in actions {
+            match action {
+                rules::Action::Mark(name) => {
+                    let windows = self
+                        .windows
+                        .iter_mut()
+                        .map(|(&w, window)| (w, window.marks_mut()));
+                    marks::mark(windows, id, name, true)?;
+                    WindowMarked(id, name.clone()).log(&mut self.logger);
+                }
+                rules::Action::Size { width, height } => {
+                    let window = self.windows.get_mut(&id).unwrap();
+                    if window.is_floating() {
+                        window.update_bounds(Bounds {
+                            width: *width,
+                            height: *height,
+                            ..*window.bounds()
+                        })?;
+                    }
+                }
+                rules::Action::Float | rules::Action::Assign(_) | rules::Action::Ignore => {}
+            }
+        }
         Ok(())
     }
 
@@ -167,9 +287,36 @@ impl WindowManager {
                 }
             }
         }
+
+        self.update_title_strips();
         Ok(())
     }
 
+    /// Redraw the title strips of every visible stacked split, labelling each
+    /// with the title of the window it represents.
+    fn update_title_strips(&mut self) {
+        let strips: Vec<_> = self
+            .displays
+            .physical_displays()
+            .values()
+            .flat_map(|pd| pd.title_strips())
+            .map(|strip| (strip, self.window_title(strip.window)))
+            .collect();
+
+        self.title_strips.draw(strips);
+    }
+
+    /// The title shown for a window: its name, falling back to its owning
+    /// application's name.
+    fn window_title(&self, id: WindowId) -> String {
+        self.windows
+            .get(&id)
+            .and_then(|w| w.cg().name().or(w.cg().owner_name()))
+            .filter(|title| !title.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| id.to_string())
+    }
+
     pub fn run(&mut self) -> Result<()> {
         let (keyboard_source, keyboard_sender) = ChannelSource::<KeyCommand>::new();
         let (workspace_source, workspace_sender) = ChannelSource::<WorkspaceEvent>::new();
@@ -217,8 +364,11 @@ impl WindowManager {
                     Event::CtlMsg { rx, mut reply } => {
                         println!("ctl sock rx");
                         println!("message on ctl sock: {rx:?}");
-                        serde_json::to_writer(&mut reply, &WmToCtlMessage::Config(self.config))
-                            .unwrap();
+                        let response = match serde_json::from_slice(&rx) {
+                            Ok(msg) => self.handle_ctl_message(msg),
+                            Err(e) => WmToCtlMessage::Failed(e.to_string()),
+                        };
+                        serde_json::to_writer(&mut reply, &response).unwrap();
                         reply.shutdown(std::net::Shutdown::Write);
                     }
                     Event::Timer => {
@@ -246,6 +396,10 @@ impl WindowManager {
                 display_id,
                 window_id,
             } => {
+                if self.ignored.remove(&window_id) {
+                    return;
+                }
+
                 if self.windows.get(&window_id).unwrap().is_minimised() {
                     // MacOS registers a Core Graphics window removed event when
                     // the application is minimised. If it is a window we
@@ -275,10 +429,7 @@ impl WindowManager {
     }
 
     fn handle_window_focus(&mut self, window_id: WindowId) -> Result<()> {
-        if let Some(pid) = self.displays.display_of_window(window_id) {
-            self.displays.set_active_physical_display(pid);
-        }
-
+        self.displays.window_focused(window_id);
         Ok(())
     }
 
@@ -288,7 +439,11 @@ impl WindowManager {
     //  2. Add window to it.
     fn handle_window_added(&mut self, id: DisplayId, cg: core_graphics::Window) -> Result<()> {
         let window_id = cg.number();
-        let window = Window::try_from(cg)?;
+        // Taking on a window focuses it, so note what had focus beforehand.
+        let previously_focused = ax_ui::Window::try_get_focused().ok();
+        let Some((window, actions)) = self.adopt_window(cg)? else {
+            return Ok(());
+        };
         let min_size = window.ax().min_size().unwrap_or_default();
         let cw = container::Window {
             id: window_id,
@@ -296,6 +451,15 @@ impl WindowManager {
             min_height: min_size.height,
         };
 
+        if let Some(lid) = assigned_logical_display(&actions)
+            && lid != self.displays.active_logical_display_id()
+        {
+            self.assign_window(window, cw, lid, id.into(), &actions)?;
+            self.update_status_bars();
+            self.apply_layout()?;
+            return self.focus_assigned_window(window_id, previously_focused);
+        }
+
         let res = self.displays.add_window(cw)?;
 
         let lid = match res {
@@ -308,6 +472,7 @@ impl WindowManager {
                     .ax()
                     .minimise()
                     .map_err(Error::AxUi)?;
+                self.apply_window_rules(window_id, &actions)?;
 
                 self.status_bars.get_mut(&id).unwrap().add_logical_id(lid);
                 self.update_status_bars();
@@ -317,6 +482,7 @@ impl WindowManager {
 
         self.status_bars.get_mut(&id).unwrap().add_logical_id(lid);
         self.windows.insert(window_id, window);
+        self.apply_window_rules(window_id, &actions)?;
         self.update_status_bars();
         self.apply_layout()?;
 
@@ -324,12 +490,88 @@ impl WindowManager {
         Ok(())
     }
 
+    // Add a window that a rule assigns to a logical display other than the
+    // active one, creating that logical display on the given physical display
+    // if it does not exist, and hiding the window if its logical display is
+    // not shown.
+    fn assign_window(
+        &mut self,
+        window: Window,
+        cw: container::Window,
+        lid: logical::Id,
+        pid: physical::Id,
+        actions: &[rules::Action],
+    ) -> Result<()> {
+        if self.displays.logical_id_owner(lid).is_none() {
+            self.displays.create_logical_display(pid, lid)?;
+        }
+        self.displays.add_window_to_logical(cw, lid)?;
+        self.windows.insert(cw.id, window);
+        self.apply_window_rules(cw.id, actions)?;
+
+        // Safety: the logical display was just created if it did not exist.
+        let pid = self.displays.logical_id_owner(lid).unwrap();
+        if self.displays.physical_displays()[&pid].active_logical_id() != lid {
+            self.windows.get_mut(&cw.id).unwrap().minimise()?;
+        }
+        if let Some(sb) = self.status_bars.get_mut(&pid.into()) {
+            sb.add_logical_id(lid);
+        }
+
+        WindowAssignedByRule(cw.id, lid).log(&mut self.logger);
+        Ok(())
+    }
+
+    // An assigned window takes focus as it appears. Follow it to its logical
+    // display when configured to, otherwise give focus back to the window that
+    // had it.
+    fn focus_assigned_window(
+        &mut self,
+        window_id: WindowId,
+        previously_focused: Option<WindowId>,
+    ) -> Result<()> {
+        let target = match self.config.focus_assigned_windows {
+            true => {
+                self.focus_managed_window(window_id)?;
+                window_id
+            }
+            false => match previously_focused {
+                Some(previous) if self.windows.contains_key(&previous) => previous,
+                _ => return Ok(()),
+            },
+        };
+
+        self.windows
+            .get(&target)
+            .unwrap()
+            .ax()
+            .try_focus()
+            .map_err(Error::AxUi)?;
+        self.update_status_bars();
+        self.update_title_strips();
+        Ok(())
+    }
+
     fn handle_window_removed(&mut self, display_id: DisplayId, window_id: WindowId) -> Result<()> {
+        if self.scratchpad.contains(&window_id) {
+            // Closing a hidden scratchpad window is taken for its minimising
+            // and never reaches here, so `handle_scratchpad_show` drops it
+            // instead. This is a shown one that has been closed.
+            self.scratchpad.retain(|&w| w != window_id);
+            self.displays.remove_floating(window_id);
+            self.windows.remove(&window_id);
+            return Ok(());
+        }
+
         if self.windows.get(&window_id).unwrap().is_floating() {
+            self.displays.remove_floating(window_id);
+            self.displays.forget_window(window_id);
+            self.windows.remove(&window_id);
             return Ok(());
         }
 
         self.displays.remove_window(display_id.into(), window_id)?;
+        self.displays.forget_window(window_id);
 
         self.windows.remove(&window_id);
         self.apply_layout()?;
@@ -381,14 +623,390 @@ impl WindowManager {
                     eprintln!("failed to resize window: {e:?}");
                 }
             }
+            KeyCommand::MoveWindow(direction) => {
+                MoveWindowInDirectionKeyCommand(direction).log(&mut self.logger);
+                if let Err(e) = self.handle_move_window(direction, None) {
+                    eprintln!("failed to move window: {e:?}");
+                }
+            }
+            KeyCommand::ToggleTabbed => {
+                ToggleTabbedKeyCommand.log(&mut self.logger);
+                if let Err(e) = self.handle_layout(LayoutChange::ToggleTabbed) {
+                    eprintln!("failed to toggle tabbed layout: {e:?}");
+                }
+            }
             KeyCommand::ToggleFloating => {
                 ToggleWindowFloatingKeyCommand.log(&mut self.logger);
                 if let Err(e) = self.handle_toggle_floating() {
                     eprintln!("failed to toggle floating: {e:?}");
                 }
             }
-            _ => {}
+            KeyCommand::Balance => {
+                BalanceKeyCommand.log(&mut self.logger);
+                if let Err(e) = self.handle_balance(BalanceArg::Container) {
+                    eprintln!("failed to balance container: {e:?}");
+                }
+            }
+        }
+    }
+
+    fn handle_ctl_message(&mut self, msg: CtlToWmMessage) -> WmToCtlMessage {
+        match msg {
+            CtlToWmMessage::GetConfig => WmToCtlMessage::Config(self.config.clone()),
+            CtlToWmMessage::GetMarks => WmToCtlMessage::Marks(self.marks()),
+            CtlToWmMessage::Command(command) => {
+                ReceivedCtlCommand(command.clone()).log(&mut self.logger);
+
+                match self.handle_command(command) {
+                    Ok(()) => WmToCtlMessage::Done,
+                    Err(e) => WmToCtlMessage::Failed(format!("{e:?}")),
+                }
+            }
+        }
+    }
+
+    fn handle_command(&mut self, command: Command) -> Result<()> {
+        match command {
+            Command::Layout(arg) => self.handle_layout(arg.into()),
+            Command::Focus(arg) => self.handle_focus_level(arg),
+            Command::Balance(arg) => self.handle_balance(arg),
+            Command::Resize(arg) => self.handle_resize_command(arg),
+            Command::Fullscreen(arg) => self.handle_fullscreen(arg),
+            Command::Scratchpad(ScratchpadArg::Move) => self.handle_move_to_scratchpad(),
+            Command::Scratchpad(ScratchpadArg::Show) => self.handle_scratchpad_show(),
+            Command::Mark(arg) => self.handle_mark(arg),
+            Command::Unmark(name) => self.handle_unmark(name),
+            Command::FocusMatching(criteria) => self.handle_focus_matching(*criteria),
+            Command::MoveToMark(name) => self.handle_move_to_mark(name),
+            Command::Workspace(WorkspaceArg::Number(n)) => {
+                self.handle_focus_logical_display(n.into())
+            }
+            Command::Workspace(WorkspaceArg::BackAndForth) => {
+                self.handle_workspace_back_and_forth()
+            }
+            Command::FocusLast => self.handle_focus_last(),
+            Command::Sticky(arg) => self.handle_sticky(arg),
+            Command::Move(arg) => self.handle_move_window(arg.direction.into(), arg.px),
+            Command::MovePosition(arg) => self.handle_move_position(arg),
+            Command::Undo => self.handle_undo(),
+            Command::Redo => self.handle_redo(),
+        }
+    }
+
+    /// Every mark currently set on a window, sorted by name.
+    fn marks(&self) -> Vec<String> {
+        marks::sorted(self.windows.values().map(Window::marks))
+    }
+
+    /// Returns the window marked with `name`.
+    fn window_with_mark(&self, name: &str) -> Result<WindowId> {
+        let windows = self.windows.iter().map(|(&id, w)| (id, w.marks()));
+        marks::marked(windows, name).ok_or_else(|| Error::MarkNotFound(name.to_string()))
+    }
+
+    /// Handle marking the currently focused window.
+    fn handle_mark(&mut self, arg: MarkArg) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+
+        let windows = self
+            .windows
+            .iter_mut()
+            .map(|(&w, window)| (w, window.marks_mut()));
+        marks::mark(windows, focused_window, &arg.name, arg.add)?;
+
+        WindowMarked(focused_window, arg.name).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Handle removing a mark from whichever window has it, or every mark from
+    /// every window when no name is given.
+    fn handle_unmark(&mut self, name: Option<String>) -> Result<()> {
+        for window in self.windows.values_mut() {
+            window.marks_mut().remove(name.as_deref());
+        }
+
+        MarkRemoved(name).log(&mut self.logger);
+        Ok(())
+    }
+
+    // To focus a window matching criteria, pick the one of those matching that
+    // was focused most recently, or the lowest when none has been, then:
+    //  - If it is managed, make it the focused window of its logical display,
+    //    then bring that logical display into view.
+    //  - If it is floating, bring the logical display it belongs to into view,
+    //    then un-minimise it if it is still hidden.
+    //  - Give the window focus.
+    fn handle_focus_matching(&mut self, criteria: Criteria) -> Result<()> {
+        let matching: Vec<_> = self
+            .windows
+            .iter()
+            .filter(|(_, w)| criteria.matches(&w.properties()))
+            .map(|(id, _)| *id)
+            .collect();
+        let target = self
+            .displays
+            .most_recently_focused(&matching)
+            .ok_or(Error::NoMatchingWindow)?;
+
+        if self.displays.display_of_window(target).is_some() {
+            self.focus_managed_window(target)?;
+        } else {
+            if let Some(owner) = self.displays.floating_owner(target) {
+                self.show_logical_display(owner)?;
+            }
+            let window = self.windows.get_mut(&target).unwrap();
+            if window.is_minimised() {
+                window.unminimise()?;
+            }
+        }
+
+        self.windows
+            .get(&target)
+            .unwrap()
+            .ax()
+            .try_focus()
+            .map_err(Error::AxUi)?;
+        self.update_status_bars();
+        self.update_title_strips();
+
+        FocusedMatchingWindow(criteria.to_string(), target).log(&mut self.logger);
+        Ok(())
+    }
+
+    // Focus a managed window within its logical display, then bring that
+    // logical display into view. The window itself is not given focus.
+    fn focus_managed_window(&mut self, target: WindowId) -> Result<()> {
+        let pid = self
+            .displays
+            .display_of_window(target)
+            .ok_or(Error::WindowNotFound)?;
+        // Safety: the window was just found on this physical display.
+        let lid = self.displays.logical_of_window(target).unwrap();
+        let pd = self.displays.physical_display_mut(pid).unwrap();
+        pd.set_focused_window(target)?;
+
+        self.show_logical_display(lid)?;
+        self.displays.window_focused(target);
+        Ok(())
+    }
+
+    // Bring a logical display into view, either by making its physical display
+    // active when it is already shown there, or by switching to it.
+    fn show_logical_display(&mut self, lid: logical::Id) -> Result<()> {
+        match self.displays.logical_id_owner(lid) {
+            Some(pid) if self.displays.physical_displays()[&pid].active_logical_id() == lid => {
+                self.displays.set_active_physical_display(pid);
+                Ok(())
+            }
+            _ => self.handle_focus_logical_display(lid),
+        }
+    }
+
+    // Focus the most recently focused managed window other than the focused
+    // one, wherever it is.
+    fn handle_focus_last(&mut self) -> Result<()> {
+        let current = ax_ui::Window::try_get_focused().ok();
+        let target = self
+            .displays
+            .last_focused_window(current)
+            .ok_or(Error::WindowNotFound)?;
+
+        self.focus_managed_window(target)?;
+        self.windows
+            .get(&target)
+            .unwrap()
+            .ax()
+            .try_focus()
+            .map_err(Error::AxUi)?;
+        self.update_status_bars();
+        self.update_title_strips();
+
+        FocusedLastWindow(target).log(&mut self.logger);
+        Ok(())
+    }
+
+    fn handle_workspace_back_and_forth(&mut self) -> Result<()> {
+        let Some(previous) = self.displays.previous_logical_display_id() else {
+            return Ok(());
+        };
+
+        self.handle_focus_logical_display(previous)?;
+        WentBackToLogicalDisplay(previous).log(&mut self.logger);
+        Ok(())
+    }
+
+    // To move the focused window to a mark:
+    //  1. Find the marked window and the logical display managing it; only
+    //     managed windows can be moved beside.
+    //  2. Checkpoint both logical displays, then take the focused window out of
+    //     its logical display, or out of the floating windows and the
+    //     scratchpad.
+    //  3. Focus the marked window within its logical display, so that the
+    //     focused window is added beside it.
+    //  4. If the marked window's logical display is not the one shown on its
+    //     physical display, hide the moved window along with it.
+    fn handle_move_to_mark(&mut self, name: String) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+        let target = self.window_with_mark(&name)?;
+        if target == focused_window {
+            return Ok(());
+        }
+
+        let target_lid = self
+            .displays
+            .logical_of_window(target)
+            .ok_or(Error::WindowNotFound)?;
+        let window = self
+            .windows
+            .get_mut(&focused_window)
+            .ok_or(Error::WindowNotFound)?;
+
+        self.displays.checkpoint_window(focused_window);
+        if self.displays.logical_of_window(focused_window) != Some(target_lid) {
+            self.displays.checkpoint_logical(target_lid);
+        }
+        if let Some(owner) = self.displays.display_of_window(focused_window) {
+            self.displays.remove_window(owner, focused_window)?;
+        } else {
+            self.scratchpad.retain(|&w| w != focused_window);
+            self.displays.remove_floating(focused_window);
+        }
+
+        window.set_floating(false);
+        let min_size = window.ax().min_size().unwrap_or_default();
+        let cw = container::Window {
+            id: focused_window,
+            min_width: min_size.width,
+            min_height: min_size.height,
+        };
+
+        // Safety: the marked window was just found on a logical display.
+        let target_pid = self.displays.logical_id_owner(target_lid).unwrap();
+        let target_pd = self.displays.physical_display_mut(target_pid).unwrap();
+        target_pd.set_focused_window(target)?;
+        let shown = target_pd.active_logical_id() == target_lid;
+
+        self.displays.add_window_to_logical(cw, target_lid)?;
+
+        let window = self.windows.get_mut(&focused_window).unwrap();
+        if shown {
+            if window.is_minimised() {
+                window.unminimise()?;
+            }
+        } else {
+            window.minimise()?;
+        }
+
+        self.apply_layout()?;
+
+        WindowMovedToMark(focused_window, name).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Handle widening or narrowing the selection of the display that owns the
+    /// currently focused window.
+    fn handle_focus_level(&mut self, arg: FocusArg) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+
+        let active_display = self.active_physical_display_mut();
+        active_display.set_focused_window(focused_window)?;
+
+        match arg {
+            FocusArg::Parent => active_display.focus_parent()?,
+            FocusArg::Child => active_display.focus_child()?,
+        }
+
+        FocusLevelChanged(arg).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Handle resetting the currently focused window's container, or the whole
+    /// active logical display, to equal shares.
+    fn handle_balance(&mut self, arg: BalanceArg) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+
+        let active_display = self.active_physical_display_mut();
+        active_display.set_focused_window(focused_window)?;
+
+        match arg {
+            BalanceArg::Container => active_display.balance()?,
+            BalanceArg::Display => active_display.balance_all()?,
+        }
+        self.apply_layout()?;
+
+        Balanced(arg).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Handle giving the currently focused window the whole of its logical
+    /// display, or returning it to its place in the layout.
+    fn handle_fullscreen(&mut self, arg: FullscreenArg) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+
+        let active_display = self.active_physical_display_mut();
+        active_display.set_focused_window(focused_window)?;
+
+        let fullscreen = match arg {
+            FullscreenArg::Enable => true,
+            FullscreenArg::Disable => false,
+            FullscreenArg::Toggle => !active_display.is_fullscreen(),
+        };
+        active_display.set_fullscreen(fullscreen)?;
+        self.apply_layout()?;
+
+        FullscreenChanged(focused_window, fullscreen).log(&mut self.logger);
+        Ok(())
+    }
+
+    // Only floating windows can be sticky, since managed windows belong to the
+    // tree of one logical display.
+    fn handle_sticky(&mut self, arg: StickyArg) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+        let window = self
+            .windows
+            .get(&focused_window)
+            .ok_or(Error::WindowNotFound)?;
+        if !window.is_floating() {
+            return Err(Error::CannotMakeTiledWindowSticky);
+        }
+
+        let sticky = match arg {
+            StickyArg::Enable => true,
+            StickyArg::Disable => false,
+            StickyArg::Toggle => self.displays.sticky_owner(focused_window).is_none(),
+        };
+        if sticky {
+            let bounds = *window.bounds();
+            self.displays.stick(focused_window, bounds);
+        } else {
+            self.displays.unstick(focused_window);
         }
+
+        StickyChanged(focused_window, sticky).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Handle changing the layout of the currently focused window's parent
+    /// split.
+    fn handle_layout(&mut self, change: LayoutChange) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+
+        self.active_physical_display_mut()
+            .set_focused_window(focused_window)?;
+        let layout = self.displays.set_layout(change)?;
+        self.apply_layout()?;
+
+        // Tabs share the same bounds, so make sure the focused one is the tab
+        // left on top.
+        self.windows
+            .get(&focused_window)
+            .unwrap()
+            .ax()
+            .try_focus()
+            .map_err(Error::AxUi)?;
+
+        LayoutChanged(focused_window, layout).log(&mut self.logger);
+        Ok(())
     }
 
     // To handle toggling a window to be floating:
@@ -398,43 +1016,180 @@ impl WindowManager {
     //     from the active physical display, and mark it as floating.
     fn handle_toggle_floating(&mut self) -> Result<()> {
         let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
-
-        if self.windows.get(&focused_window).unwrap().is_floating() {
-            self.windows
-                .get_mut(&focused_window)
-                .unwrap()
-                .set_floating(false);
-
-            let cw = container::Window {
-                id: focused_window,
-                min_width: 0.0,
-                min_height: 0.0,
-            };
-
-            self.displays.add_window(cw)?;
+        let is_floating = self
+            .windows
+            .get(&focused_window)
+            .ok_or(Error::WindowNotFound)?
+            .is_floating();
+
+        if is_floating {
+            let window = self.windows.get_mut(&focused_window).unwrap();
+            window.set_floating(false);
+            window.set_floating_bounds(window.bounds_on_screen());
+            let position = window.take_tiled_position();
+            let min_size = window.ax().min_size().unwrap_or_default();
+            self.scratchpad.retain(|&w| w != focused_window);
+            self.displays.remove_floating(focused_window);
+
+            // Put the window back where it last sat in the tree, falling back
+            // to adding it as a new window when that place has gone or is not
+            // on the active logical display.
+            self.displays
+                .checkpoint_logical(self.displays.active_logical_display_id());
+            let restored =
+                position.is_some_and(|position| self.displays.restore_window(&position).is_ok());
+            if !restored {
+                let cw = container::Window {
+                    id: focused_window,
+                    min_width: min_size.width,
+                    min_height: min_size.height,
+                };
+                self.displays.add_window(cw)?;
+            }
             self.apply_layout()?;
 
             WindowMadeManaged(focused_window).log(&mut self.logger);
         } else {
             let pid = self.displays.display_of_window(focused_window).unwrap();
+            let lid = self.displays.logical_of_window(focused_window).unwrap();
+            let position = self.displays.position_of_window(focused_window);
 
+            self.displays.checkpoint_window(focused_window);
             self.displays.remove_window(pid, focused_window)?;
-            self.windows
-                .get_mut(&focused_window)
-                .unwrap()
-                .set_floating(true);
+            self.displays.add_floating(focused_window, lid);
+            let window = self.windows.get_mut(&focused_window).unwrap();
+            window.set_floating(true);
+            window.set_tiled_position(position);
             self.apply_layout()?;
 
+            // Return the window to where it last floated, if it has before.
+            let window = self.windows.get_mut(&focused_window).unwrap();
+            if let Some(bounds) = window.floating_bounds() {
+                window.update_bounds(bounds)?;
+            }
+
             WindowMadeFloating(focused_window).log(&mut self.logger);
         }
 
         Ok(())
     }
 
+    // To move the focused window to the scratchpad:
+    //  1. If the window is managed, remove it from its logical display, the
+    //     same as toggling it floating.
+    //  2. Mark it floating, minimise it, and queue it in the scratchpad if it
+    //     is not there already.
+    //  3. Hand focus to the window its logical display now focuses.
+    fn handle_move_to_scratchpad(&mut self) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+
+        let window = self
+            .windows
+            .get_mut(&focused_window)
+            .ok_or(Error::WindowNotFound)?;
+
+        let position = self.displays.position_of_window(focused_window);
+        if let Some(pid) = self.displays.display_of_window(focused_window) {
+            self.displays.remove_window(pid, focused_window)?;
+        }
+        self.displays.remove_floating(focused_window);
+
+        window.set_floating(true);
+        if position.is_some() {
+            window.set_tiled_position(position);
+        }
+        window.minimise()?;
+        if !self.scratchpad.contains(&focused_window) {
+            self.scratchpad.push_back(focused_window);
+        }
+
+        self.apply_layout()?;
+        self.focus_active_window()?;
+
+        WindowMovedToScratchpad(focused_window).log(&mut self.logger);
+        Ok(())
+    }
+
+    // To show the scratchpad, hide, focus or show a scratchpad window as
+    // `scratchpad::next_step` decides, showing a window floating in the centre
+    // of the active physical display. A hidden window that has since been
+    // closed is dropped, and the next one in the queue is shown instead.
+    fn handle_scratchpad_show(&mut self) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().ok();
+
+        loop {
+            let windows = &self.windows;
+            let step = scratchpad::next_step(
+                &mut self.scratchpad,
+                |w| !windows[&w].is_minimised(),
+                focused_window,
+            )
+            .ok_or(Error::EmptyScratchpad)?;
+
+            match step {
+                scratchpad::Step::Hide(shown) => {
+                    self.windows.get_mut(&shown).unwrap().minimise()?;
+                    self.displays.remove_floating(shown);
+                    self.focus_active_window()?;
+                    ScratchpadWindowHidden(shown).log(&mut self.logger);
+                }
+                scratchpad::Step::Focus(shown) => {
+                    self.windows[&shown].ax().try_focus().map_err(Error::AxUi)?;
+                    ScratchpadWindowShown(shown).log(&mut self.logger);
+                }
+                scratchpad::Step::Show(next) => {
+                    let area = self.active_physical_display_mut().bounds();
+                    let window = self.windows.get_mut(&next).unwrap();
+                    match window.unminimise() {
+                        Err(Error::AxUi(ax_ui::Error::InvalidUiElement)) => {
+                            self.scratchpad.retain(|&w| w != next);
+                            self.displays.remove_floating(next);
+                            self.windows.remove(&next);
+                            continue;
+                        }
+                        result => result?,
+                    }
+                    window.centre_in(area)?;
+                    window.ax().try_focus().map_err(Error::AxUi)?;
+                    let lid = self.displays.active_logical_display_id();
+                    self.displays.add_floating(next, lid);
+                    ScratchpadWindowShown(next).log(&mut self.logger);
+                }
+            }
+
+            return Ok(());
+        }
+    }
+
+    /// Give focus to the focused window of the active logical display, if it
+    /// has any windows.
+    fn focus_active_window(&mut self) -> Result<()> {
+        if let Some(focused) = self.active_physical_display_mut().focused_window() {
+            self.windows
+                .get(&focused)
+                .unwrap()
+                .ax()
+                .try_focus()
+                .map_err(Error::AxUi)?;
+        }
+        Ok(())
+    }
+
     /// Handle a resize event in a given direction.
     fn handle_resize(&mut self, direction: Direction) -> Result<()> {
         let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
 
+        if self.is_floating(focused_window) {
+            let px = self.config.resize_amount.unwrap_or(DEFAULT_RESIZE_AMOUNT);
+            let min_size = self
+                .config
+                .min_window_size
+                .unwrap_or(DEFAULT_MIN_WINDOW_SIZE);
+            return self.place_floating_window(focused_window, |bounds, area| {
+                floating::resized_towards(bounds, direction, px, min_size, area)
+            });
+        }
+
         let active_display = self.active_physical_display_mut();
         active_display.set_focused_window(focused_window);
         active_display.resize_focused_window(direction)?;
@@ -445,10 +1200,96 @@ impl WindowManager {
         Ok(())
     }
 
-    /// Handle splitting the currently focussed window's container along the
-    /// provided axis.
+    /// Handle an explicit resize of the currently focused window's container,
+    /// or of the window itself when it is floating.
+    fn handle_resize_command(&mut self, arg: ResizeArg) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+
+        if self.is_floating(focused_window) {
+            let px = self.config.resize_amount.unwrap_or(DEFAULT_RESIZE_AMOUNT);
+            let min_size = self
+                .config
+                .min_window_size
+                .unwrap_or(DEFAULT_MIN_WINDOW_SIZE);
+            return self.place_floating_window(focused_window, |bounds, area| {
+                floating::resized(bounds, arg, px, min_size, area)
+            });
+        }
+
+        let active_display = self.active_physical_display_mut();
+        active_display.set_focused_window(focused_window)?;
+        active_display.resize_focused(arg.into())?;
+
+        self.apply_layout()?;
+
+        ContainerResized(focused_window, arg).log(&mut self.logger);
+        Ok(())
+    }
+
+    /// Handle moving the currently focused window in a direction: a tiled
+    /// window swaps places within the layout, and a floating window moves `px`
+    /// pixels, or the configured move amount.
+    fn handle_move_window(&mut self, direction: Direction, px: Option<f64>) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+
+        if self.is_floating(focused_window) {
+            let px = px
+                .or(self.config.move_amount)
+                .unwrap_or(DEFAULT_MOVE_AMOUNT);
+            return self.place_floating_window(focused_window, |bounds, area| {
+                floating::moved(bounds, direction, px, area)
+            });
+        }
+
+        let active_display = self.active_physical_display_mut();
+        active_display.set_focused_window(focused_window)?;
+        active_display.move_focused_window(direction)?;
+
+        self.apply_layout()?;
+
+        WindowMoved(focused_window, direction).log(&mut self.logger);
+        Ok(())
+    }
+
+    fn handle_move_position(&mut self, arg: PositionArg) -> Result<()> {
+        let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+        if !self.is_floating(focused_window) {
+            return Err(Error::CannotPositionTiledWindow);
+        }
+
+        self.place_floating_window(focused_window, |bounds, area| {
+            floating::positioned(bounds, arg, area)
+        })
+    }
+
+    fn is_floating(&self, id: WindowId) -> bool {
+        self.windows.get(&id).is_some_and(Window::is_floating)
+    }
+
+    // Set a floating window's bounds to those `place` gives from its bounds on
+    // screen and the part of the physical display it is on that lies between
+    // the menu bar and the status bar.
+    fn place_floating_window(
+        &mut self,
+        id: WindowId,
+        place: impl FnOnce(Bounds, Bounds) -> Bounds,
+    ) -> Result<()> {
+        let bounds = self.windows.get(&id).unwrap().bounds_on_screen();
+        let pid = self.displays.display_under(bounds);
+        let area = self.displays.physical_displays()[&pid].usable_bounds();
+
+        let placed = place(bounds, area);
+        self.windows.get_mut(&id).unwrap().update_bounds(placed)?;
+
+        FloatingWindowPlaced(id, placed).log(&mut self.logger);
+        Ok(())
+    }
+
     fn handle_split(&mut self, axis: container::Axis) -> Result<()> {
         let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
+
+        self.active_physical_display_mut()
+            .set_focused_window(focused_window)?;
         self.displays.split(axis)?;
         self.apply_layout()?;
 
@@ -456,15 +1297,62 @@ impl WindowManager {
         Ok(())
     }
 
+    fn handle_undo(&mut self) -> Result<()> {
+        let open = self.windows.keys().copied().collect();
+        let returning = self.displays.undo(&open)?;
+        self.take_back_windows(returning)?;
+
+        Undone(self.displays.active_logical_display_id()).log(&mut self.logger);
+        Ok(())
+    }
+
+    fn handle_redo(&mut self) -> Result<()> {
+        let open = self.windows.keys().copied().collect();
+        let returning = self.displays.redo(&open)?;
+        self.take_back_windows(returning)?;
+
+        Redone(self.displays.active_logical_display_id()).log(&mut self.logger);
+        Ok(())
+    }
+
+    // Undo and redo can return windows to the active logical display that have
+    // since been made floating, sent to the scratchpad, or moved to a logical
+    // display that is now hidden, so make each of them a visible managed
+    // window again before laying the display out.
+    fn take_back_windows(&mut self, windows: Vec<WindowId>) -> Result<()> {
+        for id in windows {
+            self.scratchpad.retain(|&w| w != id);
+            self.displays.remove_floating(id);
+
+            let window = self.windows.get_mut(&id).unwrap();
+            window.set_floating(false);
+            if window.is_minimised() {
+                window.unminimise()?;
+            }
+        }
+
+        self.apply_layout()?;
+        self.focus_active_window()?;
+        self.update_status_bars();
+        self.update_title_strips();
+        Ok(())
+    }
+
     fn active_physical_display_mut(&mut self) -> &mut physical::Display {
         self.displays.active_physical_display_mut()
     }
 
     fn handle_focus_shift(&mut self, direction: Direction) -> Result<()> {
-        let newly_focussed = self.active_physical_display_mut().shift_focus(direction)?;
+        let newly_focussed = self
+            .displays
+            .shift_focus(direction, self.config.focus_wrapping)?;
 
-        let window = self.windows.get_mut(&newly_focussed).unwrap();
-        window.ax().try_focus().map_err(Error::AxUi)?;
+        if let Some(window_id) = newly_focussed {
+            let window = self.windows.get_mut(&window_id).unwrap();
+            window.ax().try_focus().map_err(Error::AxUi)?;
+        }
+        self.update_status_bars();
+        self.update_title_strips();
 
         ShiftFocusInDirectionKeyCommand(direction).log(&mut self.logger);
         Ok(())
@@ -474,38 +1362,49 @@ impl WindowManager {
     ///
     /// If the target logical display ID does not exist, first create it on the
     /// currently active physical display, then move the window there.
-    // In order to do this:
-    //  1. Get the currently focused window.
-    //  2. Find the physical display that currently owns the focused window, and
-    //     the Core Graphics window that corresponds to it.
-    //  3. Try to remove the window from the source physical display.
-    //  4. Find the target physical display ID from the target logical display
-    //     ID. If no physical display manages said logical display ID, create it
-    //     on the currently active physical display.
-    //  5. Try to add the removed Core Graphics window to the target logical
-    //     display.
+    // To move the focused window to another logical display:
+    //  - Find the physical display that owns the focused window.
+    //  - If the focused window's logical display has a container selected above
+    //    it (see `focus parent`), move every window in that container;
+    //    otherwise move just the focused window.
+    //  - Remove each window from its current logical display and add it to the
+    //    target, in tree order.
+    // A floating window is given to the target instead.
     fn handle_move_focused_window_to_display(&mut self, target: logical::Id) -> Result<()> {
         let focused_window = ax_ui::Window::try_get_focused().map_err(Error::AxUi)?;
 
-        let min_size = self
-            .windows
-            .get(&focused_window)
-            .unwrap()
-            .ax()
-            .min_size()
-            .unwrap_or_default();
-
-        let window = container::Window {
-            id: focused_window,
-            min_width: min_size.width,
-            min_height: min_size.height,
+        // Find the physical display that owns the currently focused window.
+        let Some(owner) = self.displays.display_of_window(focused_window) else {
+            return self.move_floating_window_to_display(focused_window, target);
         };
+        let owner_display = &self.displays.physical_displays()[&owner];
 
-        // Find the physical display that owns the currently focused window.
-        let owner = self.displays.display_of_window(focused_window).unwrap();
-        self.displays.remove_window(owner, focused_window)?;
+        let windows = if owner_display.focused_window() == Some(focused_window) {
+            owner_display.selected_windows()
+        } else {
+            let min_size = self
+                .windows
+                .get(&focused_window)
+                .unwrap()
+                .ax()
+                .min_size()
+                .unwrap_or_default();
 
-        self.displays.add_window_to_logical(window, target)?;
+            vec![container::Window {
+                id: focused_window,
+                min_width: min_size.width,
+                min_height: min_size.height,
+            }]
+        };
+
+        self.displays.checkpoint_window(focused_window);
+        if self.displays.logical_of_window(focused_window) != Some(target) {
+            self.displays.checkpoint_logical(target);
+        }
+        for window in windows {
+            self.displays.remove_window(owner, window.id)?;
+            self.displays.add_window_to_logical(window, target)?;
+        }
 
         if let Some(focused) = self.displays.focus_display(target) {
             self.windows
@@ -522,16 +1421,42 @@ impl WindowManager {
         Ok(())
     }
 
-    /// Minimise all windows on the logical display referenced by the
-    /// provided ID.
-    fn try_minimise_logical(&mut self, id: logical::Id) -> Result<()> {
-        let Some(ld) = self.displays.get_occupied_logical(id) else {
-            return Ok(());
-        };
+    // Make a floating window, sticky or not, belong to the logical display
+    // `target`, hiding it unless that is shown on its physical display.
+    fn move_floating_window_to_display(
+        &mut self,
+        window_id: WindowId,
+        target: logical::Id,
+    ) -> Result<()> {
+        let window = self
+            .windows
+            .get_mut(&window_id)
+            .filter(|w| w.is_floating())
+            .ok_or(Error::WindowNotFound)?;
+
+        self.displays.remove_floating(window_id);
+        self.displays.add_floating(window_id, target);
+
+        let shown = self.displays.logical_id_owner(target).is_some_and(|pid| {
+            self.displays.physical_displays()[&pid].active_logical_id() == target
+        });
+        if shown {
+            if window.is_minimised() {
+                window.unminimise()?;
+            }
+        } else {
+            window.minimise()?;
+            self.focus_active_window()?;
+        }
 
-        let window_ids: Vec<_> = ld.window_ids().into_iter().collect();
+        WindowMovedToLogicalDisplay(window_id, target).log(&mut self.logger);
+        Ok(())
+    }
 
-        for w in window_ids {
+    /// Minimise all windows on the logical display referenced by the
+    /// provided ID, along with the floating windows that belong to it.
+    fn try_minimise_logical(&mut self, id: logical::Id) -> Result<()> {
+        for w in self.displays.windows_shown_with(id) {
             self.windows.get_mut(&w).unwrap().minimise()?;
         }
 
@@ -539,15 +1464,9 @@ impl WindowManager {
     }
 
     /// Un-minimise all windows on the logical display referenced by the
-    /// provided ID.
+    /// provided ID, along with the floating windows that belong to it.
     fn try_unminimise_logical(&mut self, id: logical::Id) -> Result<()> {
-        let Some(ld) = self.displays.get_occupied_logical(id) else {
-            return Ok(());
-        };
-
-        let window_ids: Vec<_> = ld.window_ids().into_iter().collect();
-
-        for w in window_ids {
+        for w in self.displays.windows_shown_with(id) {
             self.windows.get_mut(&w).unwrap().unminimise()?;
         }
 
@@ -574,6 +1493,11 @@ impl WindowManager {
     fn handle_focus_logical_display(&mut self, new_lid: logical::Id) -> Result<()> {
         let current_lid = self.displays.active_logical_display_id();
         if current_lid == new_lid {
+            // With auto back-and-forth, asking for the logical display already
+            // shown goes back to the one before it instead.
+            if self.config.workspace_auto_back_and_forth {
+                return self.handle_workspace_back_and_forth();
+            }
             return Ok(());
         }
 
@@ -630,6 +1554,14 @@ impl WindowManager {
     }
 }
 
+/// The logical display the first rule assigning a window assigns it to.
+fn assigned_logical_display(actions: &[rules::Action]) -> Option<logical::Id> {
+    actions.iter().find_map(|action| match action {
+        rules::Action::Assign(n) => Some(logical::Id::from(*n)),
+        _ => None,
+    })
+}
+
 /// Open a new "Terminal" application window.
 ///
 /// When opening a new Terminal via `open -n -a Terminal`, the OS will sometimes